@@ -0,0 +1,85 @@
+//! Compares checking every projectile against every enemy with a lookup in a [SpatialGrid].
+//! Run with `cargo bench --bench spatial_grid`.
+
+use bevy::math::Vec2;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+use test_game::{spatial_grid::SpatialGrid, ENEMY_GRID_CELL_SIZE};
+
+const ENEMY_COUNT: usize = 5_000;
+const PROJECTILE_COUNT: usize = 500;
+const PROJECTILE_RADIUS: f32 = 10.;
+/// Half the side length of the square the entities are spread over.
+const AREA: f32 = 2_000.;
+
+struct Circle {
+    pos: Vec2,
+    radius: f32,
+}
+
+fn random_circles(
+    rng: &mut SmallRng,
+    count: usize,
+    radius: impl Fn(&mut SmallRng) -> f32,
+) -> Vec<Circle> {
+    (0..count)
+        .map(|_| Circle {
+            pos: Vec2::new(rng.gen_range(-AREA..AREA), rng.gen_range(-AREA..AREA)),
+            radius: radius(rng),
+        })
+        .collect()
+}
+
+fn brute_force(enemies: &[Circle], projectiles: &[Circle]) -> usize {
+    projectiles
+        .iter()
+        .map(|projectile| {
+            enemies
+                .iter()
+                .filter(|enemy| {
+                    enemy.pos.distance(projectile.pos) <= enemy.radius + projectile.radius
+                })
+                .count()
+        })
+        .sum()
+}
+
+fn with_grid(grid: &mut SpatialGrid<usize>, enemies: &[Circle], projectiles: &[Circle]) -> usize {
+    grid.clear();
+    for (index, enemy) in enemies.iter().enumerate() {
+        grid.insert(index, enemy.pos, enemy.radius);
+    }
+    projectiles
+        .iter()
+        .map(|projectile| {
+            grid.query(projectile.pos, projectile.radius)
+                .filter(|entry| {
+                    entry.pos.distance(projectile.pos) <= entry.radius + projectile.radius
+                })
+                .count()
+        })
+        .sum()
+}
+
+fn hit_detection(c: &mut Criterion) {
+    let mut rng = SmallRng::seed_from_u64(0);
+    let enemies = random_circles(&mut rng, ENEMY_COUNT, |rng| rng.gen_range(8.0..20.0));
+    let projectiles = random_circles(&mut rng, PROJECTILE_COUNT, |_| PROJECTILE_RADIUS);
+    let mut grid = SpatialGrid::new(ENEMY_GRID_CELL_SIZE);
+    assert_eq!(
+        brute_force(&enemies, &projectiles),
+        with_grid(&mut grid, &enemies, &projectiles)
+    );
+
+    let mut group = c.benchmark_group("hit_detection_5k_enemies_500_projectiles");
+    group.bench_function("brute_force", |b| {
+        b.iter(|| brute_force(black_box(&enemies), black_box(&projectiles)))
+    });
+    group.bench_function("spatial_grid", |b| {
+        b.iter(|| with_grid(&mut grid, black_box(&enemies), black_box(&projectiles)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, hit_detection);
+criterion_main!(benches);
@@ -0,0 +1,260 @@
+use std::{f32::consts::TAU, time::Duration};
+
+use bevy::prelude::*;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    characters::components::{ContactDamage, Enemy, Health, MaxHealth},
+    cleanup,
+    map::{
+        collision::{CollisionResponse, MapCollider},
+        flow_field::IgnoresFlowField,
+    },
+    mechanics::{
+        damage::{DealDamageHitbox, TakeDamageHitbox},
+        knockback::{KnockbackResistance, KnockedBack},
+        status_effects::StatusEffects,
+    },
+    tools::rng::GameRng,
+    GameState, Heading, MovementSpeed,
+};
+
+/// Every boss that can end a run.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BossKind {
+    Wizard,
+    JotunChieftain,
+}
+
+impl BossKind {
+    const ALL: [BossKind; 2] = [BossKind::Wizard, BossKind::JotunChieftain];
+}
+
+/// The boss that enters once the run reaches [crate::characters::components::Stage::Boss].
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SelectedBoss(pub BossKind);
+
+impl Default for SelectedBoss {
+    fn default() -> Self {
+        SelectedBoss(BossKind::Wizard)
+    }
+}
+
+/// Picks the boss of the run at random.
+pub fn pick_boss(mut selected_boss: ResMut<SelectedBoss>, mut rng: ResMut<GameRng>) {
+    if let Some(&kind) = BossKind::ALL.choose(&mut **rng) {
+        *selected_boss = SelectedBoss(kind);
+    }
+}
+
+/// Marks the boss of the run, its `name` is shown above the boss health bar.
+#[derive(Component)]
+pub struct Boss {
+    pub name: &'static str,
+}
+
+/// The game is won once the boss with this marker has finished dying.
+#[derive(Component)]
+pub struct EndGameIfDead;
+
+/// Spell cast by a child entity of a boss, [BossPhase]s enable spells by kind.
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub enum BossSpell {
+    FireVolley,
+    IceSpikes,
+    Cone,
+    Charge,
+    GroundSlam,
+    Summon,
+}
+
+/// Boss spells are only cast while they have this marker.
+#[derive(Component)]
+pub struct SpellActive;
+
+/// Bosses with this marker are moved by their current attack instead of walking towards the player.
+#[derive(Component)]
+pub struct AttackMovement;
+
+/// A phase lasts while the health of the boss is at or below `health_threshold` of its max health,
+/// until the threshold of the next phase is reached.
+pub struct BossPhase {
+    pub health_threshold: f32,
+    pub movement_speed: f32,
+    pub spells: &'static [BossSpell],
+}
+
+#[derive(Component)]
+pub struct BossPhases {
+    phases: Vec<BossPhase>,
+    current: Option<usize>,
+}
+
+impl BossPhases {
+    /// `phases` are ordered from the first phase at full health to the last one.
+    pub fn new(phases: Vec<BossPhase>) -> Self {
+        Self {
+            phases,
+            current: None,
+        }
+    }
+
+    fn phase_index(&self, health_fraction: f32) -> usize {
+        self.phases
+            .iter()
+            .rposition(|phase| health_fraction <= phase.health_threshold)
+            .unwrap_or(0)
+    }
+}
+
+/// Hitboxes of a boss, only given to it once its entrance is over.
+#[derive(Component, Clone, Copy)]
+pub struct BossHitboxes {
+    pub take: TakeDamageHitbox,
+    pub deal: DealDamageHitbox,
+}
+
+/// The boss grows in while entering and can neither deal nor take damage.
+#[derive(Component, Deref, DerefMut)]
+pub struct BossEntrance(pub Timer);
+
+/// The boss fades out while dying and is despawned once the timer finishes.
+#[derive(Component, Deref, DerefMut)]
+pub struct BossDying(pub Timer);
+
+const BOSS_ENTRANCE_DURATION: Duration = Duration::from_secs(2);
+
+const BOSS_DEATH_DURATION: Duration = Duration::from_secs(2);
+
+/// Components shared by every boss, the boss still needs a [Transform] and a sprite.
+pub fn boss_bundle(
+    boss: Boss,
+    health: u32,
+    contact_damage: u32,
+    knockback_resistance: KnockbackResistance,
+    hitboxes: BossHitboxes,
+    phases: BossPhases,
+) -> impl Bundle {
+    let collider_radius = hitboxes.take.0.radius;
+    (
+        cleanup::ExitGame,
+        Enemy,
+        boss,
+        Health(health),
+        MaxHealth(health),
+        ContactDamage(contact_damage),
+        hitboxes,
+        phases,
+        BossEntrance(Timer::new(BOSS_ENTRANCE_DURATION, TimerMode::Once)),
+        MovementSpeed(0.),
+        StatusEffects::default(),
+        KnockedBack::default(),
+        MapCollider {
+            radius: collider_radius,
+            response: CollisionResponse::Slide,
+        },
+        knockback_resistance,
+        (IgnoresFlowField, Heading::default()),
+    )
+}
+
+/// TODO: This should be a system that spawns the boss at a random position around the player.
+pub(super) fn generate_boss_starting_position(pos: Vec2, rng: &mut GameRng) -> Vec2 {
+    let angle: f32 = rng.gen_range(0.0..TAU);
+    let r: f32 = rng.gen_range(50.0..100.0);
+    let x = r * angle.sin();
+    let y = r * angle.cos();
+    Vec2::new(pos.x + x, pos.y + y)
+}
+
+pub(super) fn handle_boss_entrance(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut BossEntrance, &BossHitboxes, &mut Transform), With<Boss>>,
+) {
+    for (entity, mut entrance, hitboxes, mut transform) in &mut query {
+        entrance.tick(time.delta());
+        transform.scale = Vec3::splat(entrance.fraction());
+        if entrance.is_finished() {
+            commands
+                .entity(entity)
+                .remove::<BossEntrance>()
+                .insert((hitboxes.take, hitboxes.deal));
+        }
+    }
+}
+
+/// Switches the boss to the phase matching its health, enabling the spells of that phase.
+pub(super) fn update_boss_phase(
+    mut commands: Commands,
+    mut bosses: Query<
+        (
+            &Health,
+            &MaxHealth,
+            &mut BossPhases,
+            &mut MovementSpeed,
+            &Children,
+        ),
+        (With<Boss>, Without<BossEntrance>, Without<BossDying>),
+    >,
+    spells: Query<(Entity, &BossSpell)>,
+) {
+    for (health, max_health, mut phases, mut movement_speed, children) in &mut bosses {
+        let index = phases.phase_index(**health as f32 / **max_health as f32);
+        if phases.current == Some(index) {
+            continue;
+        }
+        phases.current = Some(index);
+        let phase = &phases.phases[index];
+        **movement_speed = phase.movement_speed;
+        for (spell_entity, spell) in spells.iter_many(children) {
+            if phase.spells.contains(spell) {
+                commands.entity(spell_entity).insert(SpellActive);
+            } else {
+                commands.entity(spell_entity).remove::<SpellActive>();
+            }
+        }
+    }
+}
+
+/// Starts the death sequence of a boss once its health reaches 0.
+pub(super) fn handle_boss_death(
+    mut commands: Commands,
+    mut bosses: Query<
+        (Entity, &Health, &mut MovementSpeed, &Children),
+        (With<Boss>, Without<BossDying>),
+    >,
+    spells: Query<Entity, With<BossSpell>>,
+) {
+    for (entity, health, mut movement_speed, children) in &mut bosses {
+        if **health > 0 {
+            continue;
+        }
+        **movement_speed = 0.;
+        commands
+            .entity(entity)
+            .remove::<(TakeDamageHitbox, DealDamageHitbox)>()
+            .insert(BossDying(Timer::new(BOSS_DEATH_DURATION, TimerMode::Once)));
+        for spell_entity in spells.iter_many(children) {
+            commands.entity(spell_entity).remove::<SpellActive>();
+        }
+    }
+}
+
+pub(super) fn animate_boss_death(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut BossDying, &mut Sprite, Has<EndGameIfDead>)>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    for (entity, mut dying, mut sprite, end_game) in &mut query {
+        dying.tick(time.delta());
+        sprite.color = sprite.color.with_alpha(1. - dying.fraction());
+        if dying.is_finished() {
+            commands.entity(entity).despawn();
+            if end_game {
+                game_state.set(GameState::Win);
+            }
+        }
+    }
+}
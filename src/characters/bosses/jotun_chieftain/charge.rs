@@ -0,0 +1,143 @@
+use std::{f32::consts::FRAC_PI_2, time::Duration};
+
+use bevy::prelude::*;
+use test_game::TELEGRAPH_Z;
+
+use crate::{
+    characters::{
+        bosses::boss::{AttackMovement, BossDying, BossSpell, SpellActive},
+        components,
+    },
+    cleanup,
+    map::collision::CollisionGrid,
+    Heading,
+};
+
+/// Straight-line charge towards the player, announced by a telegraph for `wind_up`.
+#[derive(Component, Clone, Copy)]
+pub(super) struct ChargeAttack {
+    speed: f32,
+    distance: f32,
+    width: f32,
+    wind_up: Duration,
+}
+
+/// Charge of a boss, it stands still until `wind_up` is over
+/// and then charges until it has covered `remaining_distance` or runs into a solid tile of the [CollisionGrid].
+#[derive(Component)]
+pub(super) struct Charging {
+    direction: Vec2,
+    speed: f32,
+    remaining_distance: f32,
+    radius: f32,
+    wind_up: Timer,
+    telegraph: Option<Entity>,
+}
+
+pub(super) fn spawn_charge_spell(builder: &mut ChildSpawnerCommands) {
+    builder.spawn((
+        components::AttackCooldown(default()),
+        components::MaxAttackCooldown(Duration::from_secs_f32(6.0)),
+        ChargeAttack {
+            speed: 350.,
+            distance: 250.,
+            width: 40.,
+            wind_up: Duration::from_secs_f32(0.8),
+        },
+        BossSpell::Charge,
+        Transform::default(),
+    ));
+}
+
+pub(super) fn start_charge(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    player_query: Query<&Transform, With<components::Player>>,
+    boss_query: Query<&Transform, Without<Charging>>,
+    mut query: Query<
+        (
+            &ChildOf,
+            &ChargeAttack,
+            &mut components::AttackCooldown,
+            &components::MaxAttackCooldown,
+        ),
+        With<SpellActive>,
+    >,
+) {
+    let player_position = player_query
+        .single()
+        .expect("Expected a single player!")
+        .translation
+        .xy();
+    for (child_of, charge, mut charge_cooldown, &max_charge_cooldown) in &mut query {
+        let Ok(boss_transform) = boss_query.get(child_of.parent()) else {
+            continue;
+        };
+        if !charge_cooldown.is_ready(*max_charge_cooldown) {
+            continue;
+        }
+        charge_cooldown.reset(*max_charge_cooldown);
+        let pos = boss_transform.translation.xy();
+        let direction = (player_position - pos).normalize_or(Vec2::Y);
+        let telegraph = commands
+            .spawn((
+                cleanup::ExitGame,
+                Mesh2d(meshes.add(Rectangle::new(charge.width, charge.distance))),
+                MeshMaterial2d(materials.add(Color::srgba(1., 0.2, 0., 0.3))),
+                Transform::from_translation(
+                    (pos + direction * charge.distance / 2.).extend(TELEGRAPH_Z),
+                )
+                .with_rotation(Quat::from_rotation_z(direction.to_angle() - FRAC_PI_2)),
+            ))
+            .id();
+        commands.entity(child_of.parent()).insert((
+            Charging {
+                direction,
+                speed: charge.speed,
+                remaining_distance: charge.distance,
+                radius: charge.width / 2.,
+                wind_up: Timer::new(charge.wind_up, TimerMode::Once),
+                telegraph: Some(telegraph),
+            },
+            AttackMovement,
+        ));
+    }
+}
+
+/// Moves charging bosses, the charge ends early when the boss runs into a solid tile or dies.
+pub(super) fn update_charge(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(
+        Entity,
+        &mut Charging,
+        &mut Transform,
+        &mut Heading,
+        Has<BossDying>,
+    )>,
+    collision_grid: Res<CollisionGrid>,
+) {
+    for (entity, mut charging, mut transform, mut heading, dying) in &mut query {
+        *heading = Heading::new(Vec2::ZERO);
+        charging.wind_up.tick(time.delta());
+        if !charging.wind_up.is_finished() && !dying {
+            continue;
+        }
+        if let Some(telegraph) = charging.telegraph.take() {
+            commands.entity(telegraph).despawn();
+        }
+        let step = (charging.speed * time.delta_secs()).min(charging.remaining_distance);
+        let next_position = transform.translation.xy() + charging.direction * step;
+        let blocked = collision_grid.is_blocked(next_position, charging.radius);
+        if !blocked && !dying {
+            transform.translation = next_position.extend(transform.translation.z);
+            charging.remaining_distance -= step;
+        }
+        if blocked || dying || charging.remaining_distance <= 0. {
+            commands
+                .entity(entity)
+                .remove::<(Charging, AttackMovement)>();
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use test_game::TELEGRAPH_Z;
+
+use crate::{
+    characters::{
+        bosses::boss::{BossSpell, SpellActive},
+        components,
+    },
+    cleanup,
+    mechanics::{
+        cooldown::LifeTime,
+        damage::{damaging, BaseDamage, Circle, DealDamageHitbox},
+    },
+    skills::skills::EnemySkills,
+};
+
+/// Slams the ground, sending out a [Shockwave] that grows up to `max_radius` over `duration`.
+#[derive(Component, Clone, Copy)]
+pub(super) struct GroundSlam {
+    max_radius: f32,
+    duration: Duration,
+    damage: u32,
+}
+
+/// Circle hitbox that grows from the point of a [GroundSlam].
+#[derive(Component)]
+pub(super) struct Shockwave {
+    max_radius: f32,
+    timer: Timer,
+}
+
+pub(super) fn spawn_ground_slam_spell(builder: &mut ChildSpawnerCommands) {
+    builder.spawn((
+        components::AttackCooldown(default()),
+        components::MaxAttackCooldown(Duration::from_secs_f32(8.0)),
+        GroundSlam {
+            max_radius: 150.,
+            duration: Duration::from_secs_f32(1.5),
+            damage: 12,
+        },
+        BossSpell::GroundSlam,
+        Transform::default(),
+    ));
+}
+
+pub(super) fn spawn_ground_slam(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut query: Query<
+        (
+            &GlobalTransform,
+            &GroundSlam,
+            &mut components::AttackCooldown,
+            &components::MaxAttackCooldown,
+        ),
+        With<SpellActive>,
+    >,
+) {
+    for (transform, slam, mut slam_cooldown, &max_slam_cooldown) in &mut query {
+        if !slam_cooldown.is_ready(*max_slam_cooldown) {
+            continue;
+        }
+        slam_cooldown.reset(*max_slam_cooldown);
+        commands.spawn((
+            cleanup::ExitGame,
+            Shockwave {
+                max_radius: slam.max_radius,
+                timer: Timer::new(slam.duration, TimerMode::Once),
+            },
+            damaging(
+                BaseDamage(slam.damage),
+                DealDamageHitbox::Circle(Circle { radius: 0. }),
+            ),
+            LifeTime(slam.duration),
+            Mesh2d(meshes.add(Annulus::new(0.9, 1.))),
+            MeshMaterial2d(materials.add(Color::srgba(0.6, 0.4, 0.2, 0.6))),
+            Transform::from_translation(transform.translation().xy().extend(TELEGRAPH_Z))
+                .with_scale(Vec3::ZERO),
+            EnemySkills,
+        ));
+    }
+}
+
+/// Grows the hitbox and the ring of every [Shockwave].
+pub(super) fn grow_shockwaves(
+    time: Res<Time>,
+    mut query: Query<(&mut Shockwave, &mut DealDamageHitbox, &mut Transform)>,
+) {
+    for (mut shockwave, mut hitbox, mut transform) in &mut query {
+        shockwave.timer.tick(time.delta());
+        let radius = shockwave.max_radius * shockwave.timer.fraction();
+        *hitbox = DealDamageHitbox::Circle(Circle { radius });
+        transform.scale = Vec3::new(radius, radius, 1.);
+    }
+}
@@ -0,0 +1,104 @@
+mod charge;
+mod ground_slam;
+mod summon;
+
+use bevy::prelude::*;
+use charge::{spawn_charge_spell, start_charge, update_charge};
+use ground_slam::{grow_shockwaves, spawn_ground_slam, spawn_ground_slam_spell};
+use summon::{spawn_summon, spawn_summon_spell};
+use test_game::ENEMY_Z;
+
+use crate::{
+    characters::{
+        bosses::boss::{
+            boss_bundle, generate_boss_starting_position, Boss, BossHitboxes, BossKind, BossPhase,
+            BossPhases, BossSpell, EndGameIfDead, SelectedBoss,
+        },
+        components,
+    },
+    mechanics::{
+        damage::{Circle, DealDamageHitbox, TakeDamageHitbox},
+        knockback::KnockbackResistance,
+    },
+    sprites::{Character, SpriteKind, CHIEFTAIN_HEIGHT, CHIEFTAIN_WIDTH},
+    GameRng, GameState,
+};
+
+pub(super) fn jotun_chieftain_bundle(x: f32, y: f32) -> impl Bundle {
+    let radius = Vec2::new(CHIEFTAIN_HEIGHT as f32, CHIEFTAIN_WIDTH as f32).length() / 2.;
+    (
+        boss_bundle(
+            Boss {
+                name: "The Jotun Chieftain",
+            },
+            300,
+            20,
+            KnockbackResistance(0.9),
+            BossHitboxes {
+                take: TakeDamageHitbox(Circle { radius }),
+                deal: DealDamageHitbox::Circle(Circle { radius }),
+            },
+            BossPhases::new(vec![
+                BossPhase {
+                    health_threshold: 1.,
+                    movement_speed: 30.,
+                    spells: &[BossSpell::Charge, BossSpell::GroundSlam],
+                },
+                BossPhase {
+                    health_threshold: 0.6,
+                    movement_speed: 40.,
+                    spells: &[BossSpell::Charge, BossSpell::GroundSlam, BossSpell::Summon],
+                },
+                BossPhase {
+                    health_threshold: 0.3,
+                    movement_speed: 55.,
+                    spells: &[BossSpell::Charge, BossSpell::GroundSlam, BossSpell::Summon],
+                },
+            ]),
+        ),
+        Transform::from_xyz(x, y, ENEMY_Z).with_scale(Vec3::ZERO),
+        SpriteKind::Character(Character::JotunChieftain),
+        EndGameIfDead,
+    )
+}
+
+fn spawn_jotun_chieftain(
+    mut commands: Commands,
+    query: Query<&Transform, With<components::Player>>,
+    mut rng: ResMut<GameRng>,
+) {
+    let player = query
+        .single()
+        .expect("Expected a single player!")
+        .translation;
+    let enemy_position = generate_boss_starting_position(player.xy(), &mut rng);
+    commands
+        .spawn(jotun_chieftain_bundle(enemy_position.x, enemy_position.y))
+        .with_children(|spells| {
+            spawn_charge_spell(spells);
+            spawn_ground_slam_spell(spells);
+            spawn_summon_spell(spells);
+        });
+}
+
+pub(super) struct JotunChieftainBossPlugin;
+
+impl Plugin for JotunChieftainBossPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(components::Stage::Boss),
+            spawn_jotun_chieftain.run_if(resource_equals(SelectedBoss(BossKind::JotunChieftain))),
+        )
+        .add_systems(
+            Update,
+            (
+                start_charge,
+                update_charge,
+                spawn_ground_slam,
+                grow_shockwaves,
+                spawn_summon,
+            )
+                .run_if(in_state(GameState::Running).and(any_with_component::<Boss>)),
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use std::{f32::consts::TAU, time::Duration};
+
+use bevy::prelude::*;
+
+use crate::{
+    characters::{
+        bosses::boss::{BossSpell, SpellActive},
+        components,
+        enemy_definitions::EnemyDefinitions,
+        systems::mobs::spawn_enemy,
+    },
+    map::collision::CollisionGrid,
+    mechanics::cooldown::InGameTime,
+    tools::rng::GameRng,
+};
+
+/// Summons `count` minions evenly spread on a circle of `radius` around the boss.
+#[derive(Component, Clone, Copy)]
+pub(super) struct Summon {
+    enemy: &'static str,
+    count: u32,
+    radius: f32,
+}
+
+pub(super) fn spawn_summon_spell(builder: &mut ChildSpawnerCommands) {
+    builder.spawn((
+        components::AttackCooldown(default()),
+        components::MaxAttackCooldown(Duration::from_secs_f32(12.0)),
+        Summon {
+            enemy: "jotun",
+            count: 6,
+            radius: 60.,
+        },
+        BossSpell::Summon,
+        Transform::default(),
+    ));
+}
+
+pub(super) fn spawn_summon(
+    mut commands: Commands,
+    enemy_definitions: Res<EnemyDefinitions>,
+    in_game_time: Res<InGameTime>,
+    collision_grid: Res<CollisionGrid>,
+    mut rng: ResMut<GameRng>,
+    mut query: Query<
+        (
+            &GlobalTransform,
+            &Summon,
+            &mut components::AttackCooldown,
+            &components::MaxAttackCooldown,
+        ),
+        With<SpellActive>,
+    >,
+) {
+    for (transform, summon, mut summon_cooldown, &max_summon_cooldown) in &mut query {
+        if !summon_cooldown.is_ready(*max_summon_cooldown) {
+            continue;
+        }
+        summon_cooldown.reset(*max_summon_cooldown);
+        let Some(definition) = enemy_definitions.enemy(summon.enemy) else {
+            warn!("Unknown enemy summoned by boss: {}", summon.enemy);
+            continue;
+        };
+        let center = transform.translation().xy();
+        for i in 0..summon.count {
+            let angle = TAU * i as f32 / summon.count as f32;
+            let pos = center + Vec2::from_angle(angle) * summon.radius;
+            spawn_enemy(
+                &mut commands,
+                definition,
+                &in_game_time,
+                &collision_grid,
+                pos,
+                &mut rng,
+            );
+        }
+    }
+}
@@ -1,11 +1,32 @@
-use bevy::app::{App, Plugin};
+use bevy::{
+    app::{App, Plugin, Update},
+    ecs::schedule::IntoScheduleConfigs,
+    state::condition::in_state,
+};
 
+use crate::GameState;
+
+pub mod boss;
+pub mod jotun_chieftain;
 pub mod wizard;
 
 pub(super) struct BossesPlugin;
 
 impl Plugin for BossesPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(wizard::WizardBossPlugin);
+        app.add_plugins((
+            wizard::WizardBossPlugin,
+            jotun_chieftain::JotunChieftainBossPlugin,
+        ))
+        .add_systems(
+            Update,
+            (
+                boss::handle_boss_entrance,
+                boss::update_boss_phase,
+                boss::handle_boss_death,
+                boss::animate_boss_death,
+            )
+                .run_if(in_state(GameState::Running)),
+        );
     }
 }
@@ -1 +1,120 @@
+use std::{f32::consts::PI, time::Duration};
 
+use bevy::prelude::*;
+use test_game::{ENEMY_Z, TELEGRAPH_Z};
+
+use crate::{
+    characters::{
+        bosses::boss::{BossSpell, SpellActive},
+        components,
+    },
+    cleanup,
+    mechanics::{
+        cooldown::LifeTime,
+        damage::{damaging, BaseDamage, Cone, DealDamageHitbox},
+    },
+    skills::skills::EnemySkills,
+    tools::debug::cone_sector,
+};
+
+/// Cone spell that is aimed at the player and hits once its `wind_up` is over.
+#[derive(Component, Clone, Copy)]
+pub(super) struct ConeAttack {
+    range: f32,
+    angular_width: f32,
+    wind_up: Duration,
+    damage: u32,
+}
+
+/// Translucent sector showing where a [ConeAttack] is about to hit.
+#[derive(Component)]
+pub(super) struct ConeTelegraph {
+    cone: Cone,
+    damage: u32,
+    timer: Timer,
+}
+
+pub(super) fn spawn_cone_attack_spell(builder: &mut ChildSpawnerCommands) {
+    builder.spawn((
+        components::AttackCooldown(default()),
+        components::MaxAttackCooldown(Duration::from_secs_f32(5.0)),
+        ConeAttack {
+            range: 120.,
+            angular_width: PI / 6.,
+            wind_up: Duration::from_secs_f32(1.0),
+            damage: 15,
+        },
+        BossSpell::Cone,
+        Transform::default(),
+    ));
+}
+
+pub(super) fn spawn_cone_attack(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    player_query: Query<&Transform, With<components::Player>>,
+    mut query: Query<
+        (
+            &GlobalTransform,
+            &ConeAttack,
+            &mut components::AttackCooldown,
+            &components::MaxAttackCooldown,
+        ),
+        With<SpellActive>,
+    >,
+) {
+    let player_position = player_query
+        .single()
+        .expect("Expected a single player!")
+        .translation
+        .xy();
+    for (transform, cone_attack, mut cone_cooldown, &max_cone_cooldown) in &mut query {
+        if !cone_cooldown.is_ready(*max_cone_cooldown) {
+            continue;
+        }
+        cone_cooldown.reset(*max_cone_cooldown);
+        let pos = transform.translation().xy();
+        let cone = Cone {
+            mid_angle: (player_position - pos).normalize_or(Vec2::Y) * cone_attack.range,
+            angular_width: cone_attack.angular_width,
+        };
+        let (sector, rotation) = cone_sector(cone);
+        commands.spawn((
+            cleanup::ExitGame,
+            ConeTelegraph {
+                cone,
+                damage: cone_attack.damage,
+                timer: Timer::new(cone_attack.wind_up, TimerMode::Once),
+            },
+            Mesh2d(meshes.add(sector)),
+            MeshMaterial2d(materials.add(Color::srgba(1., 0.4, 0., 0.3))),
+            Transform::from_translation(pos.extend(TELEGRAPH_Z)).with_rotation(rotation),
+        ));
+    }
+}
+
+/// Replaces finished telegraphs with a short lived cone that hits the player.
+pub(super) fn resolve_cone_telegraphs(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut ConeTelegraph, &Transform)>,
+) {
+    for (entity, mut telegraph, transform) in &mut query {
+        telegraph.timer.tick(time.delta());
+        if !telegraph.timer.is_finished() {
+            continue;
+        }
+        commands.entity(entity).despawn();
+        commands.spawn((
+            cleanup::ExitGame,
+            damaging(
+                BaseDamage(telegraph.damage),
+                DealDamageHitbox::Cone(telegraph.cone),
+            ),
+            LifeTime::from_secs_f32(0.1),
+            Transform::from_translation(transform.translation.xy().extend(ENEMY_Z)),
+            EnemySkills,
+        ));
+    }
+}
@@ -5,14 +5,20 @@ use rand::Rng;
 use test_game::ENEMY_Z;
 
 use crate::{
-    characters::components,
+    characters::{
+        bosses::boss::{BossSpell, SpellActive},
+        components,
+    },
+    map::collision::{CollisionResponse, MapCollider},
     mechanics::{
         cooldown::LifeTime,
         damage::{damaging, BaseDamage, Circle, DealDamageHitbox},
         movement::orbiting::AngularVelocity,
+        status_effects::{AppliesStatus, StatusEffect, StatusEffectKind},
     },
     skills::skills::EnemySkills,
     sprites::{Skill, SpriteKind},
+    tools::rng::GameRng,
     Heading, MovementSpeed,
 };
 
@@ -29,6 +35,15 @@ fn fire_volley_bundle(pos: Vec2, angle: f32) -> impl Bundle {
             BaseDamage(10),
             DealDamageHitbox::Circle(Circle { radius: 20. }),
         ),
+        AppliesStatus(StatusEffect {
+            kind: StatusEffectKind::Burn,
+            duration: Duration::from_secs(2),
+            tick_damage: 1,
+        }),
+        MapCollider {
+            radius: 20.,
+            response: CollisionResponse::Destroy,
+        },
         SpriteKind::Skill(Skill::FireBall),
         Transform::from_translation(Vec3::new(pos.x, pos.y, ENEMY_Z)),
         EnemySkills,
@@ -40,22 +55,27 @@ pub(super) fn spawn_fire_volley_spell(builder: &mut ChildSpawnerCommands) {
         components::AttackCooldown(default()),
         components::MaxAttackCooldown(Duration::from_secs_f32(10.0)),
         FireVolleyCount(4),
+        BossSpell::FireVolley,
         Transform::default(),
     ));
 }
 
 pub(super) fn spawn_fire_volley(
     mut commands: Commands,
-    mut query: Query<(
-        &GlobalTransform,
-        &FireVolleyCount,
-        &mut components::AttackCooldown,
-        &components::MaxAttackCooldown,
-    )>,
+    mut query: Query<
+        (
+            &GlobalTransform,
+            &FireVolleyCount,
+            &mut components::AttackCooldown,
+            &components::MaxAttackCooldown,
+        ),
+        With<SpellActive>,
+    >,
+    mut rng: ResMut<GameRng>,
 ) {
     for (transform, fv_count, mut fv_cooldown, &max_fv_cooldown) in &mut query {
         for _ in 0..(fv_cooldown.reset(*max_fv_cooldown)) {
-            let start_angle: f32 = rand::thread_rng().gen_range(0.0..TAU);
+            let start_angle: f32 = rng.gen_range(0.0..TAU);
             for n in 1..=**fv_count {
                 let angle = start_angle + (n as f32 * (TAU / **fv_count as f32));
                 commands.spawn(fire_volley_bundle(transform.translation().xy(), angle));
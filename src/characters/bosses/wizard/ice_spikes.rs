@@ -5,13 +5,18 @@ use rand::Rng;
 use test_game::ENEMY_Z;
 
 use crate::{
-    characters::components,
+    characters::{
+        bosses::boss::{BossSpell, SpellActive},
+        components,
+    },
     mechanics::{
         cooldown::LifeTime,
         damage::{damaging, BaseDamage, Circle, DealDamageHitbox},
+        status_effects::{AppliesStatus, StatusEffect, StatusEffectKind},
     },
     skills::skills::EnemySkills,
     sprites::{Skill, SpriteKind},
+    tools::rng::GameRng,
     Heading, MovementSpeed,
 };
 
@@ -27,6 +32,11 @@ fn ice_spikes_bundle(pos: Vec2, angle: f32) -> impl Bundle {
             BaseDamage(12),
             DealDamageHitbox::Circle(Circle { radius: 15. }),
         ),
+        AppliesStatus(StatusEffect {
+            kind: StatusEffectKind::Freeze,
+            duration: Duration::from_millis(750),
+            tick_damage: 0,
+        }),
         SpriteKind::Skill(Skill::IceSpike),
         Transform::from_translation(Vec3::new(pos.x, pos.y, ENEMY_Z)),
         EnemySkills,
@@ -38,22 +48,27 @@ pub(super) fn spawn_ice_spikes_spell(builder: &mut ChildSpawnerCommands) {
         components::AttackCooldown(default()),
         components::MaxAttackCooldown(Duration::from_secs_f32(3.0)),
         IceSpikesCount(6),
+        BossSpell::IceSpikes,
         Transform::default(),
     ));
 }
 
 pub(super) fn spawn_ice_spikes(
     mut commands: Commands,
-    mut query: Query<(
-        &GlobalTransform,
-        &IceSpikesCount,
-        &mut components::AttackCooldown,
-        &components::MaxAttackCooldown,
-    )>,
+    mut query: Query<
+        (
+            &GlobalTransform,
+            &IceSpikesCount,
+            &mut components::AttackCooldown,
+            &components::MaxAttackCooldown,
+        ),
+        With<SpellActive>,
+    >,
+    mut rng: ResMut<GameRng>,
 ) {
     for (transform, ice_count, mut ice_cooldown, &max_ice_cooldown) in &mut query {
         for _ in 0..(ice_cooldown.reset(*max_ice_cooldown)) {
-            let start_angle: f32 = rand::thread_rng().gen_range(0.0..TAU);
+            let start_angle: f32 = rng.gen_range(0.0..TAU);
             for n in 1..=**ice_count {
                 let angle = start_angle + (n as f32 * (TAU / **ice_count as f32));
                 commands.spawn(ice_spikes_bundle(transform.translation().xy(), angle));
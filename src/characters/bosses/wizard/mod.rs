@@ -1,54 +1,65 @@
+mod cone_attack;
 mod fire_volley;
 mod ice_spikes;
 
-use std::f32::consts::{PI, TAU};
-
 use bevy::prelude::*;
+use cone_attack::{resolve_cone_telegraphs, spawn_cone_attack, spawn_cone_attack_spell};
 use fire_volley::{spawn_fire_volley, spawn_fire_volley_spell};
 use ice_spikes::{spawn_ice_spikes, spawn_ice_spikes_spell};
-use rand::prelude::*;
 use test_game::ENEMY_Z;
 
 use crate::{
-    characters::components,
-    cleanup,
-    mechanics::damage::{Circle, Cone, DealDamageHitbox, TakeDamageHitbox},
+    characters::{
+        bosses::boss::{
+            boss_bundle, generate_boss_starting_position, Boss, BossHitboxes, BossKind, BossPhase,
+            BossPhases, BossSpell, EndGameIfDead, SelectedBoss,
+        },
+        components,
+    },
+    mechanics::{
+        damage::{Circle, DealDamageHitbox, TakeDamageHitbox},
+        knockback::KnockbackResistance,
+    },
     sprites::{Character, SpriteKind, WIZARD_HEIGHT, WIZARD_WIDTH},
-    GameRng, GameState, Heading, MovementSpeed,
+    GameRng, GameState,
 };
 
-#[derive(Component)]
-pub struct EndGameIfDead;
-
 pub(super) fn wizard_bundle(x: f32, y: f32) -> impl Bundle {
+    let radius = Vec2::new(WIZARD_HEIGHT as f32, WIZARD_WIDTH as f32).length() / 2.;
     (
-        cleanup::ExitGame,
-        components::Enemy,
-        components::Health(200),
-        MovementSpeed(25.),
-        Heading::default(),
-        TakeDamageHitbox(Circle {
-            radius: Vec2::new(WIZARD_HEIGHT as f32, WIZARD_WIDTH as f32).length() / 2.,
-        }),
-        DealDamageHitbox::Cone(Cone {
-            mid_angle: Vec2::new(50., 50.),
-            angular_width: (1. / 8.) * PI,
-        }),
-        Transform::from_xyz(x, y, ENEMY_Z),
+        boss_bundle(
+            Boss { name: "The Wizard" },
+            200,
+            10,
+            KnockbackResistance(0.7),
+            BossHitboxes {
+                take: TakeDamageHitbox(Circle { radius }),
+                deal: DealDamageHitbox::Circle(Circle { radius }),
+            },
+            BossPhases::new(vec![
+                BossPhase {
+                    health_threshold: 1.,
+                    movement_speed: 25.,
+                    spells: &[BossSpell::IceSpikes, BossSpell::Cone],
+                },
+                BossPhase {
+                    health_threshold: 0.6,
+                    movement_speed: 35.,
+                    spells: &[BossSpell::IceSpikes, BossSpell::FireVolley, BossSpell::Cone],
+                },
+                BossPhase {
+                    health_threshold: 0.25,
+                    movement_speed: 50.,
+                    spells: &[BossSpell::IceSpikes, BossSpell::FireVolley, BossSpell::Cone],
+                },
+            ]),
+        ),
+        Transform::from_xyz(x, y, ENEMY_Z).with_scale(Vec3::ZERO),
         SpriteKind::Character(Character::Wizard),
         EndGameIfDead,
     )
 }
 
-/// TODO: This should be a system that spawns the boss at a random position around the player.
-fn generate_random_starting_position(pos: Vec2, rng: &mut GameRng) -> Vec2 {
-    let angle: f32 = rng.gen_range(0.0..TAU);
-    let r: f32 = rng.gen_range(50.0..100.0);
-    let x = r * angle.sin();
-    let y = r * angle.cos();
-    Vec2::new(pos.x + x, pos.y + y)
-}
-
 /// TODO: This should be a system only run when it has not spawned yet,
 /// and should stop running once it has spawned.
 pub fn spawn_boss(
@@ -61,35 +72,33 @@ pub fn spawn_boss(
         .single()
         .expect("Expected a single player!")
         .translation;
-    let enemy_position = generate_random_starting_position(player.xy(), &mut rng);
+    let enemy_position = generate_boss_starting_position(player.xy(), &mut rng);
     commands
         .spawn(wizard_bundle(enemy_position.x, enemy_position.y))
         .with_children(|spells| {
             spawn_fire_volley_spell(spells);
             spawn_ice_spikes_spell(spells);
+            spawn_cone_attack_spell(spells);
         });
 }
 
-pub(super) fn check_for_victory(
-    mut game_state: ResMut<NextState<GameState>>,
-    query: Query<&components::Health, With<EndGameIfDead>>,
-) {
-    if let Some(health) = query.iter().next() {
-        if **health == 0 {
-            game_state.set(GameState::Win);
-        }
-    }
-}
-
 pub(super) struct WizardBossPlugin;
 
 impl Plugin for WizardBossPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(components::Stage::Wizard), spawn_boss)
-            .add_systems(
-                Update,
-                (spawn_fire_volley, spawn_ice_spikes, check_for_victory)
-                    .run_if(in_state(components::Stage::Wizard)),
-            );
+        app.add_systems(
+            OnEnter(components::Stage::Boss),
+            spawn_boss.run_if(resource_equals(SelectedBoss(BossKind::Wizard))),
+        )
+        .add_systems(
+            Update,
+            (
+                spawn_fire_volley,
+                spawn_ice_spikes,
+                spawn_cone_attack,
+                resolve_cone_telegraphs,
+            )
+                .run_if(in_state(GameState::Running).and(any_with_component::<Boss>)),
+        );
     }
 }
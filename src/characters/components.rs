@@ -2,11 +2,13 @@ use std::time::Duration;
 
 use bevy::{
     ecs::{component::Component, resource::Resource},
+    math::Vec2,
     prelude::{Deref, DerefMut},
 };
 
 use crate::{
     mechanics::cooldown::{CooldownComponent, CooldownResource},
+    sprites::SpriteKind,
     Heading,
 };
 
@@ -52,12 +54,38 @@ pub struct AttackDirection(pub Heading);
 #[derive(Component, Deref, DerefMut, Clone, Copy)]
 pub struct PlayerDamage(pub u32);
 
+/// Crit and damage variance stats of the player, rolled on every hit dealt by the player.
+#[derive(Component, Clone, Copy)]
+pub struct DamageRoll {
+    /// Chance between 0 and 1 for a hit to be a critical hit.
+    pub crit_chance: f32,
+    pub crit_multiplier: f32,
+    /// Damage of a hit is randomly changed by up to this fraction, in both directions.
+    pub variance: f32,
+}
+
 #[derive(Component, Deref, DerefMut)]
 pub struct Health(pub u32);
 
+/// Flat reduction of every hit the player takes, a hit always deals at least 1 damage.
+#[derive(Component, Deref, DerefMut, Clone, Copy, Default)]
+pub struct Armor(pub u32);
+
+impl Armor {
+    pub fn reduce(&self, damage: u32) -> u32 {
+        damage.saturating_sub(self.0).max(1)
+    }
+}
+
 #[derive(Component, Deref, DerefMut, Clone, Copy)]
 pub struct MaxHealth(pub u32);
 
+#[derive(Component, Deref, DerefMut, Default)]
+pub struct HealthRegenCooldown(pub CooldownComponent);
+
+#[derive(Component, Deref, DerefMut, Clone, Copy)]
+pub struct MaxHealthRegenCooldown(pub Duration);
+
 // <-- MOBS -->
 
 #[derive(Resource, Deref, DerefMut)]
@@ -69,15 +97,79 @@ pub struct SpawnCooldown(pub CooldownResource);
 #[derive(Component)]
 pub struct Enemy;
 
-// <-- BOSS -->
-use bevy::state::state::SubStates;
+/// Damage dealt to the player when touching the enemy, before the [Armor] of the player.
+#[derive(Component, Deref, DerefMut, Clone, Copy)]
+pub struct ContactDamage(pub u32);
+
+/// Amount of XP dropped when the enemy dies.
+#[derive(Component, Deref, DerefMut, Clone, Copy)]
+pub struct XpDrop(pub f32);
+
+/// Id of the loot table rolled when the enemy dies.
+#[derive(Component, Deref, Clone)]
+pub struct LootTableId(pub String);
+
+/// Stronger, tinted enemies spawned by the wave director.
+#[derive(Component)]
+pub struct Elite;
+
+/// Enemies with a [RangedBehaviour] keep between `min_distance` and `max_distance` from the player,
+/// strafing around them while in that band.
+#[derive(Component, Clone, Copy)]
+pub struct RangedBehaviour {
+    pub min_distance: f32,
+    pub max_distance: f32,
+    /// 1 for counterclockwise strafing, -1 for clockwise.
+    pub strafe_direction: f32,
+}
+
+/// Projectile fired by enemies with a [RangedBehaviour].
+#[derive(Component, Clone, Copy)]
+pub struct RangedAttack {
+    pub damage: u32,
+    pub hitbox_radius: f32,
+    pub sprite: SpriteKind,
+}
+
+/// Changes the point an enemy walks towards, so that the horde spreads out around the player.
+#[derive(Component, Clone, Copy)]
+pub enum Steering {
+    /// Aims up to `offset` to the side of the player, `side` is 1 for the left and -1 for the right.
+    Flank { offset: f32, side: f32 },
+    /// Walks to the point at `angle` on a circle with `radius` around the player before closing in.
+    Encircle { radius: f32, angle: f32 },
+}
 
-use crate::GameState;
+impl Steering {
+    /// The point an enemy at `pos` walks towards.
+    pub fn target(&self, pos: Vec2, player_pos: Vec2) -> Vec2 {
+        let to_player = player_pos - pos;
+        match *self {
+            Steering::Flank { offset, side } => {
+                let offset = offset.min(to_player.length() / 2.);
+                player_pos + to_player.perp().normalize_or_zero() * offset * side
+            }
+            Steering::Encircle { radius, angle } => {
+                if to_player.length() > radius * 1.5 {
+                    player_pos + Vec2::from_angle(angle) * radius
+                } else {
+                    player_pos
+                }
+            }
+        }
+    }
+}
+
+// <-- BOSS -->
+use bevy::state::state::States;
 
-#[derive(SubStates, Clone, Debug, PartialEq, Eq, Hash, Default)]
-#[source(GameState = GameState::Running)]
+/// Stage of the current run, set by the wave timeline and reset at the start of every run.
+/// Not a sub state of [crate::GameState::Running], so it survives level ups and pauses.
+#[derive(States, Clone, Copy, Debug, PartialEq, Eq, Hash, Default, serde::Deserialize)]
 pub enum Stage {
     #[default]
     Start,
-    Wizard,
+    Middle,
+    Late,
+    Boss,
 }
@@ -0,0 +1,216 @@
+use std::{fs, time::Duration};
+
+use bevy::prelude::*;
+use rand::distributions::{Distribution, WeightedIndex};
+use test_game::ENEMY_DEFINITIONS_FILE;
+
+use crate::{
+    loot::loot::{LootKind, LootTable, LootTableEntry},
+    map::biome::Biome,
+    mechanics::status_effects::{StatusEffect, StatusEffectKind},
+    sprites::{Character, SpriteKind, ENEMY_HEIGHT, ENEMY_WIDTH},
+    tools::rng::GameRng,
+};
+
+/// Health of an enemy depending on how long the run has lasted.
+#[derive(serde::Deserialize, Clone, Copy)]
+pub struct HealthCurve {
+    pub base: u32,
+    pub increase_per_minute: f32,
+}
+
+impl HealthCurve {
+    pub fn health_at(&self, in_game_time: Duration) -> u32 {
+        self.base + (in_game_time.as_secs_f32() / 60. * self.increase_per_minute).floor() as u32
+    }
+}
+
+/// Makes the enemy keep its distance from the player and shoot projectiles at them.
+#[derive(serde::Deserialize, Clone, Copy)]
+pub struct RangedDefinition {
+    pub min_distance: f32,
+    pub max_distance: f32,
+    pub attack_cooldown_secs: f32,
+    pub projectile_speed: f32,
+    pub projectile_range: f32,
+    pub projectile_damage: u32,
+    pub projectile_radius: f32,
+    pub projectile_sprite: SpriteKind,
+}
+
+/// Status effect an enemy applies to the player with its contact damage and its projectiles.
+#[derive(serde::Deserialize, Clone, Copy)]
+pub struct StatusDefinition {
+    pub kind: StatusEffectKind,
+    pub duration_secs: f32,
+    #[serde(default)]
+    pub tick_damage: u32,
+}
+
+impl StatusDefinition {
+    pub fn effect(&self) -> StatusEffect {
+        StatusEffect {
+            kind: self.kind,
+            duration: Duration::from_secs_f32(self.duration_secs),
+            tick_damage: self.tick_damage,
+        }
+    }
+}
+
+/// Makes the enemy approach the player from the side or from a point around them.
+#[derive(serde::Deserialize, Clone, Copy)]
+pub enum SteeringDefinition {
+    Flank { offset: f32 },
+    Encircle { radius: f32 },
+}
+
+fn default_scale() -> f32 {
+    1.
+}
+
+/// Describes an enemy archetype, see `assets/data/enemies.json`.
+#[derive(serde::Deserialize, Clone)]
+pub struct EnemyDefinition {
+    pub id: String,
+    pub sprite: SpriteKind,
+    pub health: HealthCurve,
+    pub speed: f32,
+    pub hitbox_radius: f32,
+    pub contact_damage: u32,
+    pub xp: f32,
+    #[serde(default)]
+    pub loot_table: Option<String>,
+    #[serde(default = "default_scale")]
+    pub scale: f32,
+    #[serde(default)]
+    pub ranged: Option<RangedDefinition>,
+    #[serde(default)]
+    pub steering: Option<SteeringDefinition>,
+    #[serde(default)]
+    pub on_hit: Option<StatusDefinition>,
+}
+
+/// Entry of the spawn table, the enemy can only spawn between `from_secs` and `until_secs`.
+#[derive(serde::Deserialize, Clone)]
+pub struct SpawnTableEntry {
+    pub enemy: String,
+    pub weight: u32,
+    #[serde(default)]
+    pub from_secs: f32,
+    #[serde(default)]
+    pub until_secs: Option<f32>,
+}
+
+impl SpawnTableEntry {
+    fn is_active(&self, in_game_time: Duration) -> bool {
+        let secs = in_game_time.as_secs_f32();
+        self.from_secs <= secs && self.until_secs.is_none_or(|until| secs < until)
+    }
+}
+
+/// Spawn table used instead of the default one while the enemy spawns in `biome`.
+#[derive(serde::Deserialize, Clone)]
+pub struct BiomeSpawnTable {
+    pub biome: Biome,
+    pub entries: Vec<SpawnTableEntry>,
+}
+
+/// All enemy archetypes, loot tables and spawn tables, loaded from [ENEMY_DEFINITIONS_FILE].
+#[derive(Resource, serde::Deserialize, Clone)]
+pub struct EnemyDefinitions {
+    pub enemies: Vec<EnemyDefinition>,
+    pub loot_tables: Vec<LootTable>,
+    pub spawn_table: Vec<SpawnTableEntry>,
+    #[serde(default)]
+    pub biome_spawn_tables: Vec<BiomeSpawnTable>,
+}
+
+impl Default for EnemyDefinitions {
+    /// Only the Jotun, used if the definitions file is missing or invalid.
+    fn default() -> Self {
+        Self {
+            enemies: vec![EnemyDefinition {
+                id: "jotun".to_string(),
+                sprite: SpriteKind::Character(Character::Jotun),
+                health: HealthCurve {
+                    base: 100,
+                    increase_per_minute: 20.,
+                },
+                speed: 100.,
+                hitbox_radius: Vec2::new(ENEMY_HEIGHT as f32, ENEMY_WIDTH as f32).length() / 2.,
+                contact_damage: 10,
+                xp: 10.,
+                loot_table: Some("common".to_string()),
+                scale: 1.,
+                ranged: None,
+                steering: None,
+                on_hit: None,
+            }],
+            loot_tables: vec![LootTable {
+                id: "common".to_string(),
+                none_weight: 6,
+                entries: [
+                    LootKind::Potion,
+                    LootKind::ThorsHammer,
+                    LootKind::Magnet,
+                    LootKind::Coin,
+                ]
+                .into_iter()
+                .map(|loot| LootTableEntry { loot, weight: 1 })
+                .collect(),
+            }],
+            spawn_table: vec![SpawnTableEntry {
+                enemy: "jotun".to_string(),
+                weight: 1,
+                from_secs: 0.,
+                until_secs: None,
+            }],
+            biome_spawn_tables: Vec::new(),
+        }
+    }
+}
+
+impl EnemyDefinitions {
+    pub fn load() -> Option<EnemyDefinitions> {
+        let json_str = fs::read_to_string(ENEMY_DEFINITIONS_FILE).ok()?;
+        match serde_json::from_str(&json_str) {
+            Ok(definitions) => Some(definitions),
+            Err(err) => {
+                error!("Invalid enemy definitions in {ENEMY_DEFINITIONS_FILE}: {err}");
+                None
+            }
+        }
+    }
+
+    pub fn enemy(&self, id: &str) -> Option<&EnemyDefinition> {
+        self.enemies.iter().find(|enemy| enemy.id == id)
+    }
+
+    pub fn loot_table(&self, id: &str) -> Option<&LootTable> {
+        self.loot_tables.iter().find(|table| table.id == id)
+    }
+
+    /// The spawn table of `biome`, or the default one if the biome has none.
+    pub fn spawn_table(&self, biome: Biome) -> &[SpawnTableEntry] {
+        self.biome_spawn_tables
+            .iter()
+            .find(|table| table.biome == biome)
+            .map_or(&self.spawn_table, |table| &table.entries)
+    }
+
+    /// Picks an enemy from the entries of the spawn table of `biome` that are active at `in_game_time`.
+    pub fn pick_enemy(
+        &self,
+        in_game_time: Duration,
+        biome: Biome,
+        rng: &mut GameRng,
+    ) -> Option<&EnemyDefinition> {
+        let active: Vec<&SpawnTableEntry> = self
+            .spawn_table(biome)
+            .iter()
+            .filter(|entry| entry.is_active(in_game_time))
+            .collect();
+        let weights = WeightedIndex::new(active.iter().map(|entry| entry.weight)).ok()?;
+        self.enemy(&active[weights.sample(&mut **rng)].enemy)
+    }
+}
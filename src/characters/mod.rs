@@ -2,31 +2,31 @@ use bevy::prelude::*;
 use bosses::BossesPlugin;
 
 use crate::{
-    characters::{components::Stage, systems::player::spawn_player_hero},
+    characters::{systems::player::spawn_player_hero, wave_director::run_wave_director},
     AppState, GameState,
 };
 pub mod bosses;
 pub mod components;
+pub mod enemy_definitions;
 pub mod systems;
+pub mod wave_director;
 
 pub struct MobPlugin;
 
 impl Plugin for MobPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(BossesPlugin)
-            .add_systems(
-                Update,
-                (
-                    systems::mobs::spawn_enemies,
-                    systems::mobs::update_enemies,
-                    systems::mobs::update_enemy_spawn_rate,
-                )
-                    .run_if(in_state(GameState::Running)),
+        app.add_plugins(BossesPlugin).add_systems(
+            Update,
+            (
+                systems::mobs::spawn_enemies,
+                systems::mobs::update_enemies,
+                systems::mobs::update_ranged_enemies,
+                systems::mobs::ranged_enemy_shooting,
+                systems::mobs::update_enemy_spawn_rate,
+                run_wave_director,
             )
-            .add_systems(
-                Update,
-                (systems::mobs::handle_stages_wizard).run_if(in_state(Stage::Start)),
-            );
+                .run_if(in_state(GameState::Running)),
+        );
     }
 }
 
@@ -41,6 +41,7 @@ impl Plugin for PlayerPlugin {
                     systems::player::player_attack_facing_from_mouse,
                     systems::player::handle_player_death,
                     systems::player::handle_player_xp,
+                    systems::player::handle_player_health_regen,
                     systems::player::player_shooting,
                 )
                     .run_if(in_state(GameState::Running)),
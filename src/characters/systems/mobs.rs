@@ -1,91 +1,424 @@
+use crate::characters::bosses::boss::AttackMovement;
 use crate::characters::components;
+use crate::characters::enemy_definitions::{EnemyDefinition, EnemyDefinitions, SteeringDefinition};
+use crate::characters::wave_director::WaveDirector;
+use crate::map::collision::{CollisionGrid, CollisionResponse, MapCollider};
+use crate::map::flow_field::{FlowField, IgnoresFlowField};
+use crate::map::map_plugin::{world_to_tile, MapNoise};
 use crate::mechanics::cooldown::InGameTime;
-use crate::mechanics::damage::{Circle, DealDamageHitbox, TakeDamageHitbox};
-use crate::sprites::{Character, SpriteKind, ENEMY_HEIGHT, ENEMY_WIDTH};
+use crate::mechanics::damage::{damaging, BaseDamage, Circle, DealDamageHitbox, TakeDamageHitbox};
+use crate::mechanics::knockback::KnockedBack;
+use crate::mechanics::movement::{projectile, ShouldRotate};
+use crate::mechanics::spatial::EnemyGrid;
+use crate::mechanics::status_effects::{AppliesStatus, StatusEffectKind, StatusEffects};
+use crate::skills::skills::EnemySkills;
 use crate::tools::rng::GameRng;
 use crate::Heading;
 use crate::{cleanup, MovementSpeed};
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
-use std::time::Duration;
-use test_game::{ENEMY_Z, INITIAL_SPAWN_RATE, SPAWN_RATE_INCREASE, WIZARD_SPAWN_TIME};
+use rand::Rng;
+use std::{f32::consts::TAU, time::Duration};
+use test_game::{ENEMY_Z, INITIAL_SPAWN_RATE, SPAWN_RATE_INCREASE};
 
-fn jotun_bundle(health: u32, x: f32, y: f32) -> impl Bundle {
-    let radius = Vec2::new(ENEMY_HEIGHT as f32, ENEMY_WIDTH as f32).length() / 2.;
+fn enemy_bundle(definition: &EnemyDefinition, health: u32, x: f32, y: f32) -> impl Bundle {
+    let radius = definition.hitbox_radius;
     (
         cleanup::ExitGame,
         components::Enemy,
         components::Health(health),
-        MovementSpeed(100.),
+        components::ContactDamage(definition.contact_damage),
+        components::XpDrop(definition.xp),
+        MovementSpeed(definition.speed),
+        StatusEffects::default(),
+        KnockedBack::default(),
         Heading::default(),
         DealDamageHitbox::Circle(Circle { radius }),
         TakeDamageHitbox(Circle { radius }),
-        Transform::from_xyz(x, y, ENEMY_Z),
-        SpriteKind::Character(Character::Jotun),
+        MapCollider {
+            radius,
+            response: CollisionResponse::Slide,
+        },
+        Transform::from_xyz(x, y, ENEMY_Z).with_scale(Vec3::splat(definition.scale)),
+        definition.sprite,
     )
 }
 
+/// Spawns an enemy from its definition, with health scaled by the time spent in game.
+/// Spawn points inside trees or rocks are moved to the nearest tile the enemy fits on.
+pub fn spawn_enemy(
+    commands: &mut Commands,
+    definition: &EnemyDefinition,
+    in_game_time: &InGameTime,
+    collision_grid: &CollisionGrid,
+    pos: Vec2,
+    rng: &mut GameRng,
+) -> Entity {
+    let pos = collision_grid.nearest_free(pos, definition.hitbox_radius);
+    let health = definition.health.health_at(in_game_time.time());
+    let mut enemy = commands.spawn(enemy_bundle(definition, health, pos.x, pos.y));
+    if let Some(loot_table) = &definition.loot_table {
+        enemy.insert(components::LootTableId(loot_table.clone()));
+    }
+    if let Some(ranged) = definition.ranged {
+        enemy.insert((
+            components::RangedBehaviour {
+                min_distance: ranged.min_distance,
+                max_distance: ranged.max_distance,
+                strafe_direction: if rng.gen_bool(0.5) { 1. } else { -1. },
+            },
+            components::RangedAttack {
+                damage: ranged.projectile_damage,
+                hitbox_radius: ranged.projectile_radius,
+                sprite: ranged.projectile_sprite,
+            },
+            components::ProjectileSpeed(ranged.projectile_speed),
+            components::Range(ranged.projectile_range),
+            components::AttackCooldown(default()),
+            components::MaxAttackCooldown(Duration::from_secs_f32(ranged.attack_cooldown_secs)),
+        ));
+    }
+    if let Some(on_hit) = definition.on_hit {
+        enemy.insert(AppliesStatus(on_hit.effect()));
+    }
+    if let Some(steering) = definition.steering {
+        enemy.insert(match steering {
+            SteeringDefinition::Flank { offset } => components::Steering::Flank {
+                offset,
+                side: if rng.gen_bool(0.5) { 1. } else { -1. },
+            },
+            SteeringDefinition::Encircle { radius } => components::Steering::Encircle {
+                radius,
+                angle: rng.gen_range(0.0..TAU),
+            },
+        });
+    }
+    enemy.id()
+}
+
+/// Index of the sprite atlas frame that faces closest to `direction`.
+fn facing_atlas_index(direction: Vec2) -> usize {
+    [
+        Vec2::new(0., -1.),
+        Vec2::new(0., 1.),
+        Vec2::new(1., 0.),
+        Vec2::new(-1., 0.),
+    ]
+    .into_iter()
+    .enumerate()
+    .max_by(|(_, v1), (_, v2)| v1.dot(direction).partial_cmp(&v2.dot(direction)).unwrap())
+    .unwrap()
+    .0
+}
+
+/// How strongly overlapping enemies push each other apart compared to walking towards their target.
+const SEPARATION_WEIGHT: f32 = 3.;
+/// Smallest push of an overlapping neighbour, so that overlaps are resolved instead of only shrinking.
+const MIN_SEPARATION_PUSH: f32 = 0.1;
+
+/// Direction that pushes `entity` away from `neighbour` when both are on the exact same spot.
+/// It only depends on the pair, so the two are always pushed in opposite directions,
+/// and it differs between pairs, so a pile of enemies on one spot spreads out.
+fn coincident_push(entity: Entity, neighbour: Entity) -> Vec2 {
+    let (low, high) = if entity < neighbour {
+        (entity, neighbour)
+    } else {
+        (neighbour, entity)
+    };
+    let mut hash = low.to_bits() ^ high.to_bits().rotate_left(32);
+    hash = (hash ^ (hash >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    hash = (hash ^ (hash >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    let direction = Vec2::from_angle((hash >> 40) as f32 / (1 << 24) as f32 * TAU);
+    if entity == low {
+        direction
+    } else {
+        -direction
+    }
+}
+
+/// Direction that pushes the enemy at `pos` out of the hitboxes of its neighbours,
+/// longer the more they overlap.
+fn separation(entity: Entity, pos: Vec2, radius: f32, enemy_grid: &EnemyGrid) -> Vec2 {
+    enemy_grid
+        .query(pos, radius)
+        .filter(|neighbour| neighbour.item != entity)
+        .map(|neighbour| {
+            let min_distance = radius + neighbour.radius;
+            let away = pos - neighbour.pos;
+            let distance = away.length();
+            if distance >= min_distance {
+                return Vec2::ZERO;
+            }
+            let direction = if distance > f32::EPSILON {
+                away / distance
+            } else {
+                coincident_push(entity, neighbour.item)
+            };
+            direction * (1. - distance / min_distance).max(MIN_SEPARATION_PUSH)
+        })
+        .sum()
+}
+
+/// Enemies this many tiles or closer to a solid tile follow the [FlowField] instead of walking straight.
+const FLOW_FIELD_TILE_DISTANCE: i32 = 2;
+
+/// Direction towards the player along the [FlowField], only for enemies close to obstacles.
+fn path_direction(
+    pos: Vec2,
+    flow_field: &FlowField,
+    collision_grid: &CollisionGrid,
+) -> Option<Vec2> {
+    let tile = world_to_tile(pos);
+    if !collision_grid.is_near_solid(tile, FLOW_FIELD_TILE_DISTANCE) {
+        return None;
+    }
+    flow_field.direction(tile)
+}
+
+/// Enemies walk towards the player, or their [components::Steering] target, while keeping apart from each other.
+/// Close to obstacles they follow the [FlowField] around them, unless they have [IgnoresFlowField].
+/// The resulting [Heading] is normalized, so crowding never makes an enemy faster than its [MovementSpeed].
 pub fn update_enemies(
     q_pl: Query<&Transform, With<components::Player>>,
     mut q_enmy: Query<
-        (&Transform, &mut Heading, &mut Sprite),
+        (
+            Entity,
+            &Transform,
+            &TakeDamageHitbox,
+            Option<&components::Steering>,
+            &mut Heading,
+            &mut Sprite,
+            &KnockedBack,
+            Has<IgnoresFlowField>,
+        ),
+        (
+            With<components::Enemy>,
+            Without<components::Player>,
+            Without<components::RangedBehaviour>,
+            Without<AttackMovement>,
+        ),
+    >,
+    enemy_grid: Res<EnemyGrid>,
+    flow_field: Res<FlowField>,
+    collision_grid: Res<CollisionGrid>,
+) {
+    let player_position = q_pl
+        .single()
+        .expect("Expected a single entity!")
+        .translation
+        .xy();
+    for (
+        entity,
+        enmy_trans,
+        hitbox,
+        steering,
+        mut heading,
+        mut sprite,
+        knocked_back,
+        ignores_flow_field,
+    ) in &mut q_enmy
+    {
+        if knocked_back.is_stunned() {
+            *heading = Heading::new(Vec2::ZERO);
+            continue;
+        }
+        let enemy_pos = enmy_trans.translation.xy();
+        let target = steering.map_or(player_position, |steering| {
+            steering.target(enemy_pos, player_position)
+        });
+        let path = if ignores_flow_field {
+            None
+        } else {
+            path_direction(enemy_pos, &flow_field, &collision_grid)
+        };
+        let seek = path.unwrap_or_else(|| (target - enemy_pos).normalize_or_zero());
+        let separation = separation(entity, enemy_pos, hitbox.0.radius, &enemy_grid);
+        *heading = Heading::new(seek + separation * SEPARATION_WEIGHT);
+        if let Some(atlas) = &mut sprite.texture_atlas {
+            atlas.index = facing_atlas_index(player_position - enemy_pos);
+        }
+    }
+}
+
+/// Ranged enemies walk towards the player until they are within `max_distance`,
+/// back off when closer than `min_distance` and strafe around the player in between.
+pub fn update_ranged_enemies(
+    q_pl: Query<&Transform, With<components::Player>>,
+    mut q_enmy: Query<
+        (
+            Entity,
+            &Transform,
+            &TakeDamageHitbox,
+            &components::RangedBehaviour,
+            &mut Heading,
+            &mut Sprite,
+            &KnockedBack,
+        ),
         (With<components::Enemy>, Without<components::Player>),
     >,
+    enemy_grid: Res<EnemyGrid>,
+    flow_field: Res<FlowField>,
+    collision_grid: Res<CollisionGrid>,
 ) {
     let player_position = q_pl
         .single()
         .expect("Expected a single entity!")
         .translation
         .xy();
-    for (enmy_trans, mut heading, mut sprite) in &mut q_enmy {
+    for (entity, enmy_trans, hitbox, behaviour, mut heading, mut sprite, knocked_back) in
+        &mut q_enmy
+    {
+        if knocked_back.is_stunned() {
+            *heading = Heading::new(Vec2::ZERO);
+            continue;
+        }
         let enemy_pos = enmy_trans.translation.xy();
-        *heading = Heading::new(-(enemy_pos - player_position));
+        let to_player = player_position - enemy_pos;
+        let distance = to_player.length();
+        let direction = if distance > behaviour.max_distance {
+            path_direction(enemy_pos, &flow_field, &collision_grid).unwrap_or(to_player)
+        } else if distance < behaviour.min_distance {
+            -to_player
+        } else {
+            to_player.perp() * behaviour.strafe_direction
+        };
+        let separation = separation(entity, enemy_pos, hitbox.0.radius, &enemy_grid);
+        *heading = Heading::new(direction.normalize_or_zero() + separation * SEPARATION_WEIGHT);
         if let Some(atlas) = &mut sprite.texture_atlas {
-            atlas.index = [
-                Vec2::new(0., -1.),
-                Vec2::new(0., 1.),
-                Vec2::new(1., 0.),
-                Vec2::new(-1., 0.),
-            ]
-            .into_iter()
-            .enumerate()
-            .max_by(|(_, v1), (_, v2)| v1.dot(**heading).partial_cmp(&v2.dot(**heading)).unwrap())
-            .unwrap()
-            .0;
+            atlas.index = facing_atlas_index(to_player);
+        }
+    }
+}
+
+/// Ranged enemies shoot at the player whenever their attack is ready and the player is within their band.
+pub fn ranged_enemy_shooting(
+    mut commands: Commands,
+    q_pl: Query<&Transform, With<components::Player>>,
+    mut q_enmy: Query<
+        (
+            &Transform,
+            &components::RangedBehaviour,
+            &components::RangedAttack,
+            &components::ProjectileSpeed,
+            &components::Range,
+            &mut components::AttackCooldown,
+            &components::MaxAttackCooldown,
+            Option<&StatusEffects>,
+            Option<&AppliesStatus>,
+            &KnockedBack,
+        ),
+        (With<components::Enemy>, Without<components::Player>),
+    >,
+) {
+    let player_position = q_pl
+        .single()
+        .expect("Expected a single entity!")
+        .translation
+        .xy();
+    for (
+        enmy_trans,
+        behaviour,
+        attack,
+        &projectile_speed,
+        &range,
+        mut attack_cooldown,
+        max_attack_cooldown,
+        status_effects,
+        applies_status,
+        knocked_back,
+    ) in &mut q_enmy
+    {
+        let enemy_pos = enmy_trans.translation.xy();
+        let stunned = knocked_back.is_stunned()
+            || status_effects.is_some_and(|effects| effects.has(StatusEffectKind::Stun));
+        if stunned || enemy_pos.distance(player_position) > behaviour.max_distance {
+            attack_cooldown.wait();
+            continue;
+        }
+        for _ in 0..attack_cooldown.reset(**max_attack_cooldown) {
+            let mut shot = commands.spawn((
+                projectile(
+                    Heading::new(player_position - enemy_pos),
+                    MovementSpeed(*projectile_speed),
+                    range,
+                    ShouldRotate(true),
+                ),
+                damaging(
+                    BaseDamage(attack.damage),
+                    DealDamageHitbox::Circle(Circle {
+                        radius: attack.hitbox_radius,
+                    }),
+                ),
+                MapCollider {
+                    radius: attack.hitbox_radius,
+                    response: CollisionResponse::Destroy,
+                },
+                attack.sprite,
+                Transform::from_translation(enemy_pos.extend(ENEMY_Z)),
+                EnemySkills,
+            ));
+            if let Some(&applies_status) = applies_status {
+                shot.insert(applies_status);
+            }
         }
     }
 }
 
-fn generate_random_starting_position(pos: Vec2, rng: &mut GameRng) -> Vec2 {
+pub fn generate_random_starting_position(pos: Vec2, rng: &mut GameRng) -> Vec2 {
     pos + rng.rand_vec(500., 1000.)
 }
 
+/// What [spawn_enemies] picks from and where: the enemy pool, the quiet phases of the timeline
+/// and the map around the player.
+#[derive(SystemParam)]
+pub struct SpawnContext<'w> {
+    enemy_definitions: Res<'w, EnemyDefinitions>,
+    wave_director: Res<'w, WaveDirector>,
+    map_noise: Res<'w, MapNoise>,
+    collision_grid: Res<'w, CollisionGrid>,
+}
+
 pub fn spawn_enemies(
     mut commands: Commands,
     query: Query<&Transform, With<components::Player>>,
-    _time: Res<Time>,
     mut spawncooldown: ResMut<components::SpawnCooldown>,
     spawnrate: Res<components::SpawnRate>,
     mut rng: ResMut<GameRng>,
     in_game_time: Res<InGameTime>,
+    spawn: SpawnContext,
 ) {
+    let SpawnContext {
+        enemy_definitions,
+        wave_director,
+        map_noise,
+        collision_grid,
+    } = spawn;
+    if wave_director.is_quiet(in_game_time.time()) {
+        spawncooldown.fill();
+        return;
+    }
     for _ in 0..spawncooldown.reset(**spawnrate) {
         let player = query
             .single()
             .expect("Expected a single entity!")
             .translation;
         let enemy_position = generate_random_starting_position(player.xy(), &mut rng);
-        commands.spawn(jotun_bundle(
-            get_enemy_health(&in_game_time),
-            enemy_position.x,
-            enemy_position.y,
-        ));
+        let biome = map_noise.biomes().biome_at(enemy_position);
+        let Some(definition) = enemy_definitions.pick_enemy(in_game_time.time(), biome, &mut rng)
+        else {
+            continue;
+        };
+        spawn_enemy(
+            &mut commands,
+            definition,
+            &in_game_time,
+            &collision_grid,
+            enemy_position,
+            &mut rng,
+        );
     }
 }
 
-fn get_enemy_health(in_game_time: &Res<InGameTime>) -> u32 {
-    100 + (in_game_time.time().as_secs_f32() / 3.0).floor() as u32
-}
-
 pub fn update_enemy_spawn_rate(
     mut spawnrate: ResMut<components::SpawnRate>,
     in_game_time: Res<InGameTime>,
@@ -99,11 +432,74 @@ pub fn update_enemy_spawn_rate(
     }
 }
 
-pub fn handle_stages_wizard(
-    game_time: Res<InGameTime>,
-    mut stage: ResMut<NextState<components::Stage>>,
-) {
-    if game_time.time() >= WIZARD_SPAWN_TIME {
-        stage.set(components::Stage::Wizard);
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    const RADIUS: f32 = 10.;
+    /// Distance an enemy walks per update.
+    const STEP: f32 = 1.;
+
+    fn spawn_enemy_at(world: &mut World, pos: Vec2) -> Entity {
+        world
+            .spawn((
+                components::Enemy,
+                Transform::from_translation(pos.extend(ENEMY_Z)),
+                TakeDamageHitbox(Circle { radius: RADIUS }),
+                Heading::default(),
+                Sprite::default(),
+                KnockedBack::default(),
+            ))
+            .id()
+    }
+
+    /// Rebuilds the [EnemyGrid], runs [update_enemies] and moves every enemy one [STEP] along its [Heading].
+    fn update(world: &mut World) {
+        let mut grid = EnemyGrid::default();
+        let mut enemies = world
+            .query_filtered::<(Entity, &Transform, &TakeDamageHitbox), With<components::Enemy>>();
+        for (entity, transform, hitbox) in enemies.iter(world) {
+            grid.insert(entity, transform.translation.xy(), hitbox.0.radius);
+        }
+        world.insert_resource(grid);
+        world
+            .run_system_once(update_enemies)
+            .expect("Failed to update the enemies");
+        let mut moving =
+            world.query_filtered::<(&Heading, &mut Transform), With<components::Enemy>>();
+        for (heading, mut transform) in moving.iter_mut(world) {
+            transform.translation += (**heading * STEP).extend(0.);
+        }
+    }
+
+    #[test]
+    fn enemies_on_the_same_spot_separate() {
+        let mut world = World::new();
+        world.init_resource::<FlowField>();
+        world.init_resource::<CollisionGrid>();
+        let first = spawn_enemy_at(&mut world, Vec2::ZERO);
+        let second = spawn_enemy_at(&mut world, Vec2::ZERO);
+        // The player is far away and square to the push, so walking towards them never cancels it.
+        let player_pos = coincident_push(first, second).perp() * 1000.;
+        world.spawn((
+            components::Player,
+            Transform::from_translation(player_pos.extend(0.)),
+        ));
+        for _ in 0..100 {
+            update(&mut world);
+        }
+        let pos = |entity| world.get::<Transform>(entity).unwrap().translation.xy();
+        assert!(pos(first).distance(pos(second)) >= 2. * RADIUS);
+    }
+
+    #[test]
+    fn coincident_push_is_opposite_for_the_pair() {
+        let mut world = World::new();
+        let [a, b, c] = [(); 3].map(|_| world.spawn_empty().id());
+        assert_eq!(coincident_push(a, b), -coincident_push(b, a));
+        assert!(coincident_push(a, b).is_normalized());
+        assert_ne!(coincident_push(a, b), coincident_push(a, c));
     }
 }
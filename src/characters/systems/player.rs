@@ -1,11 +1,18 @@
 use bevy::prelude::*;
-use test_game::{PLAYER_Z, PROJECTILES_Z, XP_SCALING_FACTOR};
+use test_game::{
+    BASE_CRIT_CHANCE, BASE_CRIT_MULTIPLIER, DAMAGE_VARIANCE, PLAYER_BASE_HEALTH,
+    PLAYER_HEAL_AMOUNT, PLAYER_Z, PROJECTILES_Z, XP_SCALING_FACTOR,
+};
 
 use std::time::Duration;
 
 use crate::characters::components;
+use crate::map::collision::{CollisionResponse, MapCollider};
 use crate::mechanics::damage::{self, damaging, BaseDamage, HitList, TakeDamageHitbox};
+use crate::mechanics::knockback::Knockback;
 use crate::mechanics::movement::{projectile, ShouldRotate};
+use crate::mechanics::status_effects::StatusEffects;
+use crate::prestige::stats::Stats;
 use crate::sound::events::{PlaySoundEffectEvent, PlayerSound, SkillSound, SoundEffectKind};
 use crate::sprites::{Character, Skill, SpriteKind, PLAYER_HEIGHT, PLAYER_WIDTH};
 use crate::tools::damage_tracking::DamageTrackerKind;
@@ -20,7 +27,8 @@ fn projectile_stats(
     (damage, projectile_speed, range)
 }
 
-fn player_bundle() -> impl Bundle {
+fn player_bundle(stats: &Stats) -> impl Bundle {
+    let max_health = PLAYER_BASE_HEALTH + stats.maximum_health.get_increase();
     (
         (
             cleanup::ExitGame,
@@ -41,19 +49,36 @@ fn player_bundle() -> impl Bundle {
             components::CurrentLevel(1),
             components::MaxLevel(100),
             components::XpPickUpRadius(100.0 * SCALE),
-            components::Health(2),
+            components::Health(max_health),
         ),
-        components::MaxHealth(2),
+        components::MaxHealth(max_health),
+        components::Armor::default(),
+        components::DamageRoll {
+            crit_chance: BASE_CRIT_CHANCE + stats.crit_chance.get_increase(),
+            crit_multiplier: BASE_CRIT_MULTIPLIER,
+            variance: DAMAGE_VARIANCE,
+        },
+        StatusEffects::default(),
         Transform::from_xyz(0.0, 0.0, PLAYER_Z),
         TakeDamageHitbox(damage::Circle {
             radius: Vec2::new(PLAYER_HEIGHT as f32, PLAYER_WIDTH as f32).length() / 2.,
         }),
+        MapCollider {
+            radius: Vec2::new(PLAYER_HEIGHT as f32, PLAYER_WIDTH as f32).length() / 2.,
+            response: CollisionResponse::Slide,
+        },
         SpriteKind::Character(Character::Warrior),
     )
 }
 
-pub fn spawn_player_hero(mut commands: Commands) {
-    commands.spawn(player_bundle());
+pub fn spawn_player_hero(mut commands: Commands, stats: Res<Stats>) {
+    let mut player = commands.spawn(player_bundle(&stats));
+    if let Some(regen_period) = stats.health_regen.get_increase() {
+        player.insert((
+            components::HealthRegenCooldown(default()),
+            components::MaxHealthRegenCooldown(regen_period),
+        ));
+    }
 }
 
 pub fn player_attack_facing_from_mouse(
@@ -136,6 +161,14 @@ fn player_shoot(
             Quat::from_axis_angle(Vec3::new(0., 0., 1.0), diff.y.atan2(diff.x)),
         ),
         HitList::default(),
+        MapCollider {
+            radius: 10.,
+            response: CollisionResponse::Bounce,
+        },
+        Knockback {
+            impulse: 250.,
+            hit_stun: Duration::from_millis(150),
+        },
         DamageTrackerKind::PrimaryAttack,
     ));
     sound_event.write(PlaySoundEffectEvent(SoundEffectKind::Skill(
@@ -178,3 +211,84 @@ pub fn handle_player_death(
         game_state.set(GameState::Loss);
     }
 }
+
+/// Heals the player by [PLAYER_HEAL_AMOUNT] every regen period, but only while health is missing.
+/// The timer is held at zero while at full health so a heal never triggers right after taking damage.
+pub fn handle_player_health_regen(
+    mut player_query: Query<
+        (
+            &mut components::Health,
+            &components::MaxHealth,
+            &mut components::HealthRegenCooldown,
+            &components::MaxHealthRegenCooldown,
+        ),
+        With<components::Player>,
+    >,
+) {
+    let Ok((mut health, max_health, mut regen_cooldown, max_regen_cooldown)) =
+        player_query.single_mut()
+    else {
+        return;
+    };
+    if **health == 0 || **max_health <= **health {
+        regen_cooldown.fill();
+        return;
+    }
+    for _ in 0..regen_cooldown.reset(**max_regen_cooldown) {
+        **health = (**health + PLAYER_HEAL_AMOUNT).min(**max_health);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::prestige::stats::UpgradeOptions;
+
+    fn spawn_player(stats: Stats) -> World {
+        let mut world = World::new();
+        world.insert_resource(stats);
+        world
+            .run_system_once(spawn_player_hero)
+            .expect("Failed to spawn the player");
+        world
+    }
+
+    #[test]
+    fn player_without_prestige_has_base_health() {
+        let mut world = spawn_player(Stats::default());
+        let (health, max_health, regen) = world
+            .query::<(
+                &components::Health,
+                &components::MaxHealth,
+                Option<&components::MaxHealthRegenCooldown>,
+            )>()
+            .single(&world)
+            .expect("Expected a single player!");
+        assert_eq!(**health, PLAYER_BASE_HEALTH);
+        assert_eq!(**max_health, PLAYER_BASE_HEALTH);
+        assert!(regen.is_none());
+    }
+
+    #[test]
+    fn player_uses_prestige_tiers() {
+        let mut stats = Stats::default();
+        for _ in 0..3 {
+            stats.upgrade(UpgradeOptions::MaximumHealth);
+        }
+        stats.upgrade(UpgradeOptions::HealthRegen);
+        let mut world = spawn_player(stats);
+        let (health, max_health, regen) = world
+            .query::<(
+                &components::Health,
+                &components::MaxHealth,
+                &components::MaxHealthRegenCooldown,
+            )>()
+            .single(&world)
+            .expect("Expected a single player!");
+        assert_eq!(**health, PLAYER_BASE_HEALTH + 30);
+        assert_eq!(**max_health, PLAYER_BASE_HEALTH + 30);
+        assert_eq!(**regen, Duration::from_secs(55));
+    }
+}
@@ -0,0 +1,194 @@
+use std::{f32::consts::TAU, fs, time::Duration};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use test_game::WAVE_TIMELINE_FILE;
+
+use crate::{
+    characters::{
+        components::{Elite, Health, Player, Stage},
+        enemy_definitions::EnemyDefinitions,
+        systems::mobs::{generate_random_starting_position, spawn_enemy},
+    },
+    map::collision::CollisionGrid,
+    mechanics::cooldown::InGameTime,
+    sprites::SpriteTint,
+    tools::rng::GameRng,
+};
+
+/// Something that happens at a fixed point of the run.
+#[derive(serde::Deserialize, Clone)]
+pub enum WaveEvent {
+    /// Spawns `count` enemies evenly spread on a circle of `radius` around the player.
+    Horde {
+        enemy: String,
+        count: u32,
+        radius: f32,
+    },
+    /// Spawns a single tinted enemy with its health multiplied by `health_multiplier`.
+    Elite {
+        enemy: String,
+        health_multiplier: f32,
+        tint: [f32; 3],
+    },
+    /// Stops the regular spawns for `duration_secs`.
+    Quiet { duration_secs: f32 },
+    /// Moves the run to the next [Stage].
+    Stage(Stage),
+    /// Brings in the boss of the run.
+    BossEntrance,
+}
+
+#[derive(serde::Deserialize, Clone)]
+pub struct TimelineEntry {
+    pub at_secs: f32,
+    pub event: WaveEvent,
+}
+
+/// Every [WaveEvent] of a run, loaded from [WAVE_TIMELINE_FILE].
+#[derive(Resource, serde::Deserialize, Clone)]
+pub struct WaveTimeline {
+    pub events: Vec<TimelineEntry>,
+}
+
+impl Default for WaveTimeline {
+    /// Only the boss entrance, used if the timeline file is missing or invalid.
+    fn default() -> Self {
+        Self {
+            events: vec![TimelineEntry {
+                at_secs: 240.,
+                event: WaveEvent::BossEntrance,
+            }],
+        }
+    }
+}
+
+impl WaveTimeline {
+    pub fn load() -> Option<WaveTimeline> {
+        let json_str = fs::read_to_string(WAVE_TIMELINE_FILE).ok()?;
+        match serde_json::from_str::<WaveTimeline>(&json_str) {
+            Ok(mut timeline) => {
+                timeline
+                    .events
+                    .sort_by(|e1, e2| e1.at_secs.total_cmp(&e2.at_secs));
+                Some(timeline)
+            }
+            Err(err) => {
+                error!("Invalid wave timeline in {WAVE_TIMELINE_FILE}: {err}");
+                None
+            }
+        }
+    }
+}
+
+/// Progress through the [WaveTimeline] of the current run.
+#[derive(Resource, Default)]
+pub struct WaveDirector {
+    next_event: usize,
+    quiet_until: Duration,
+}
+
+impl WaveDirector {
+    /// Whether the regular spawns are paused by a [WaveEvent::Quiet].
+    pub fn is_quiet(&self, in_game_time: Duration) -> bool {
+        in_game_time < self.quiet_until
+    }
+}
+
+pub fn reset_wave_director(
+    mut director: ResMut<WaveDirector>,
+    mut stage: ResMut<NextState<Stage>>,
+) {
+    *director = WaveDirector::default();
+    stage.set(Stage::Start);
+}
+
+/// The timeline, how far the [WaveDirector] has come through it and the [Stage] it sets.
+#[derive(SystemParam)]
+pub struct DirectorState<'w> {
+    timeline: Res<'w, WaveTimeline>,
+    director: ResMut<'w, WaveDirector>,
+    stage: ResMut<'w, NextState<Stage>>,
+}
+
+/// Triggers every event of the timeline whose time has come.
+pub fn run_wave_director(
+    mut commands: Commands,
+    state: DirectorState,
+    enemy_definitions: Res<EnemyDefinitions>,
+    in_game_time: Res<InGameTime>,
+    collision_grid: Res<CollisionGrid>,
+    player_query: Query<&Transform, With<Player>>,
+    mut rng: ResMut<GameRng>,
+) {
+    let DirectorState {
+        timeline,
+        mut director,
+        mut stage,
+    } = state;
+    let now = in_game_time.time();
+    let player_position = player_query
+        .single()
+        .expect("Expected a single player!")
+        .translation
+        .xy();
+    while let Some(entry) = timeline.events.get(director.next_event) {
+        if Duration::from_secs_f32(entry.at_secs) > now {
+            break;
+        }
+        director.next_event += 1;
+        match &entry.event {
+            WaveEvent::Horde {
+                enemy,
+                count,
+                radius,
+            } => {
+                let Some(definition) = enemy_definitions.enemy(enemy) else {
+                    warn!("Unknown enemy in wave timeline: {enemy}");
+                    continue;
+                };
+                for i in 0..*count {
+                    let angle = TAU * i as f32 / *count as f32;
+                    let pos = player_position + Vec2::from_angle(angle) * *radius;
+                    spawn_enemy(
+                        &mut commands,
+                        definition,
+                        &in_game_time,
+                        &collision_grid,
+                        pos,
+                        &mut rng,
+                    );
+                }
+            }
+            WaveEvent::Elite {
+                enemy,
+                health_multiplier,
+                tint: [red, green, blue],
+            } => {
+                let Some(definition) = enemy_definitions.enemy(enemy) else {
+                    warn!("Unknown enemy in wave timeline: {enemy}");
+                    continue;
+                };
+                let pos = generate_random_starting_position(player_position, &mut rng);
+                let health = definition.health.health_at(now) as f32 * health_multiplier;
+                let elite = spawn_enemy(
+                    &mut commands,
+                    definition,
+                    &in_game_time,
+                    &collision_grid,
+                    pos,
+                    &mut rng,
+                );
+                commands.entity(elite).insert((
+                    Elite,
+                    Health(health as u32),
+                    SpriteTint(Color::srgb(*red, *green, *blue)),
+                ));
+            }
+            WaveEvent::Quiet { duration_secs } => {
+                director.quiet_until = now + Duration::from_secs_f32(*duration_secs);
+            }
+            WaveEvent::Stage(next_stage) => stage.set(*next_stage),
+            WaveEvent::BossEntrance => stage.set(Stage::Boss),
+        }
+    }
+}
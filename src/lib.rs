@@ -1,4 +1,4 @@
-use std::time::Duration;
+pub mod spatial_grid;
 
 /// Z-value for tile layer 1.
 pub const TILE_LAYER_1_Z: f32 = 1.;
@@ -9,6 +9,9 @@ pub const TILE_LAYER_2_Z: f32 = 2.;
 /// Z-value for tile layer 3.
 pub const TILE_LAYER_3_Z: f32 = 3.;
 
+/// Z-value for telegraphs of incoming attacks.
+pub const TELEGRAPH_Z: f32 = 5.;
+
 /// Z-value for all projectiles.
 pub const PROJECTILES_Z: f32 = 20.;
 
@@ -33,14 +36,38 @@ pub const STATS_SAVE_FILE: &str = "save/stats.json";
 /// Location of save file.
 pub const SETTINGS_SAVE_FILE: &str = "save/settings.json";
 
+/// Location of the enemy definitions.
+pub const ENEMY_DEFINITIONS_FILE: &str = "assets/data/enemies.json";
+
+/// Location of the wave timeline.
+pub const WAVE_TIMELINE_FILE: &str = "assets/data/waves.json";
+
 /// Initial spawn rate for enemies (enemies per second).
 pub const INITIAL_SPAWN_RATE: f32 = 1.0;
 
 /// Amount by which the spawn rate increases every minute.
 pub const SPAWN_RATE_INCREASE: f32 = 0.2;
 
-/// Time in seconds for the wizard boss to spawn.
-pub const WIZARD_SPAWN_TIME: Duration = Duration::from_secs(10);
+/// Maximum health of the player before any prestige upgrades.
+pub const PLAYER_BASE_HEALTH: u32 = 20;
+
+/// Health restored by a potion or a health regen tick.
+pub const PLAYER_HEAL_AMOUNT: u32 = 10;
+
+/// Chance for a hit of the player to be critical before any upgrades.
+pub const BASE_CRIT_CHANCE: f32 = 0.05;
+
+/// Damage multiplier of critical hits.
+pub const BASE_CRIT_MULTIPLIER: f32 = 2.0;
+
+/// Fraction by which the damage of each hit of the player randomly varies.
+pub const DAMAGE_VARIANCE: f32 = 0.1;
+
+/// Cell size of the spatial grid of enemy hitboxes.
+pub const ENEMY_GRID_CELL_SIZE: f32 = 64.;
+
+/// Cell size of the spatial grid of xp orbs and loot drops.
+pub const PICKUP_GRID_CELL_SIZE: f32 = 32.;
 
 /// Scaling factor for xp required to level up.
 pub const XP_SCALING_FACTOR: f32 = 25.0;
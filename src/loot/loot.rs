@@ -1,16 +1,20 @@
-use crate::characters::components::{Enemy, Health, MaxHealth, Player};
+use crate::characters::bosses::boss::Boss;
+use crate::characters::components::{Enemy, Health, LootTableId, MaxHealth, Player, XpDrop};
+use crate::characters::enemy_definitions::EnemyDefinitions;
 use crate::loot::coin::handle_coin_pickup;
 use crate::loot::xp::activate_all_xp_orbs;
 use crate::mechanics::cooldown::LifeTime;
 use crate::mechanics::damage::{damaging, BaseDamage, DealDamageHitbox, HitList};
+use crate::mechanics::knockback::Knockback;
+use crate::mechanics::spatial::PickupGrid;
 use crate::prestige::stats::Stats;
 use crate::sprites::{Item, SpriteKind};
 use crate::SCALE;
 use crate::{cleanup, GameRng};
 use bevy::prelude::*;
-use rand::prelude::*;
+use rand::distributions::{Distribution, WeightedIndex};
 use std::time::Duration;
-use test_game::LOOT_DROPS_Z;
+use test_game::{LOOT_DROPS_Z, PLAYER_HEAL_AMOUNT};
 
 use super::coin::spawn_coin;
 use super::xp::{spawn_xp, MagnetActive};
@@ -27,6 +31,50 @@ fn spawn_loot(id: u32, sprite_kind: SpriteKind, x: f32, y: f32) -> impl Bundle {
     )
 }
 
+/// Kinds of loot an enemy can drop.
+#[derive(serde::Deserialize, Clone, Copy)]
+pub enum LootKind {
+    Potion,
+    ThorsHammer,
+    Magnet,
+    Coin,
+}
+
+impl LootKind {
+    fn id(&self) -> u32 {
+        match self {
+            LootKind::Potion => 0,
+            LootKind::ThorsHammer => 1,
+            LootKind::Magnet => 2,
+            LootKind::Coin => 3,
+        }
+    }
+}
+
+#[derive(serde::Deserialize, Clone, Copy)]
+pub struct LootTableEntry {
+    pub loot: LootKind,
+    pub weight: u32,
+}
+
+/// Weighted list of loot, `none_weight` is the weight of dropping nothing.
+#[derive(serde::Deserialize, Clone)]
+pub struct LootTable {
+    pub id: String,
+    pub none_weight: u32,
+    pub entries: Vec<LootTableEntry>,
+}
+
+impl LootTable {
+    /// Rolls the table, returns `None` if nothing drops.
+    pub fn roll(&self, rng: &mut GameRng) -> Option<LootKind> {
+        let weights =
+            std::iter::once(self.none_weight).chain(self.entries.iter().map(|entry| entry.weight));
+        let index = WeightedIndex::new(weights).ok()?.sample(&mut **rng);
+        Some(self.entries.get(index.checked_sub(1)?)?.loot)
+    }
+}
+
 #[derive(Component)]
 pub struct AnimationIndices {
     pub first: usize,
@@ -39,37 +87,59 @@ pub struct AnimationIndices {
 pub struct AnimationTimer(pub Timer);
 
 /// Spawns loot.
-fn try_spawn_loot(rng: &mut ResMut<GameRng>, commands: &mut Commands, pos: Vec3) {
-    let loot_id = rng.gen_range(0..10);
-    if loot_id == 3 {
-        commands.spawn(spawn_coin(loot_id, pos.x, pos.y));
+fn try_spawn_loot(
+    rng: &mut ResMut<GameRng>,
+    commands: &mut Commands,
+    pos: Vec3,
+    loot_table: &LootTable,
+) {
+    let Some(loot_kind) = loot_table.roll(rng) else {
         return;
-    }
-    let sprite_kind = match loot_id {
-        0 => SpriteKind::Item(Item::Potion),
-        1 => SpriteKind::Item(Item::ThorsHammer),
-        2 => SpriteKind::Item(Item::Magnet),
-        _ => return,
     };
-    commands.spawn(spawn_loot(loot_id, sprite_kind, pos.x, pos.y));
+    let sprite_kind = match loot_kind {
+        LootKind::Coin => {
+            commands.spawn(spawn_coin(loot_kind.id(), pos.x, pos.y));
+            return;
+        }
+        LootKind::Potion => SpriteKind::Item(Item::Potion),
+        LootKind::ThorsHammer => SpriteKind::Item(Item::ThorsHammer),
+        LootKind::Magnet => SpriteKind::Item(Item::Magnet),
+    };
+    commands.spawn(spawn_loot(loot_kind.id(), sprite_kind, pos.x, pos.y));
 }
 
+/// XP dropped by enemies without an [XpDrop].
+const DEFAULT_XP_DROP: f32 = 10.;
+
 /// Checks for dead enemies and will spawn loot accordingly.
+/// Bosses are left alone, they go through their own death sequence.
 pub fn check_for_dead_enemies(
     mut commands: Commands,
-    query: Query<(&Transform, Entity, &Health), With<Enemy>>,
+    query: Query<
+        (
+            &Transform,
+            Entity,
+            &Health,
+            Option<&XpDrop>,
+            Option<&LootTableId>,
+        ),
+        (With<Enemy>, Without<Boss>),
+    >,
+    enemy_definitions: Res<EnemyDefinitions>,
     mut rng: ResMut<GameRng>,
 ) {
-    for (transform, entity, health) in query.iter() {
+    for (transform, entity, health, xp_drop, loot_table_id) in query.iter() {
         if **health == 0 {
             commands.entity(entity).despawn();
-            // 1/5 -> 20%
             commands.spawn(spawn_xp(
-                10.,
+                xp_drop.map_or(DEFAULT_XP_DROP, |xp| **xp),
                 transform.translation.x,
                 transform.translation.y,
             ));
-            try_spawn_loot(&mut rng, &mut commands, transform.translation);
+            if let Some(loot_table) = loot_table_id.and_then(|id| enemy_definitions.loot_table(id))
+            {
+                try_spawn_loot(&mut rng, &mut commands, transform.translation, loot_table);
+            }
         }
     }
 }
@@ -88,17 +158,21 @@ pub fn pickup_loot(
     query_loot: Query<(&Transform, &LootId, Entity)>,
     mut query_xp: Query<&mut MagnetActive>,
     mut stats: ResMut<Stats>,
+    pickup_grid: Res<PickupGrid>,
 ) {
     let (player_trans, mut health, max_health) = query_player.single_mut().expect("err");
     let player_pos = player_trans.translation.xy();
-    for (loot_trans, loot, ent) in &query_loot {
+    const ITEM_PICKUP_RANGE: f32 = 50.;
+    for entry in pickup_grid.query(player_pos, ITEM_PICKUP_RANGE * SCALE) {
+        let Ok((loot_trans, loot, ent)) = query_loot.get(entry.item) else {
+            continue;
+        };
         let loot_position = loot_trans.translation.xy();
-        const ITEM_PICKUP_RANGE: f32 = 50.;
         if is_collision(player_pos, loot_position, ITEM_PICKUP_RANGE * SCALE, 0.) {
             match **loot {
                 0 => {
                     if **health < **max_health {
-                        **health += 1;
+                        **health = (**health + PLAYER_HEAL_AMOUNT).min(**max_health);
                     } else {
                         continue;
                     }
@@ -124,6 +198,10 @@ pub fn spawn_bomb(commands: &mut Commands, pos: Vec2) {
         damaging(BaseDamage(1000), DealDamageHitbox::Global),
         LifeTime(Duration::from_secs_f32(1.)),
         HitList::default(),
+        Knockback {
+            impulse: 800.,
+            hit_stun: Duration::from_millis(400),
+        },
         Transform {
             translation: Vec3::new(pos.x, pos.y, LOOT_DROPS_Z),
             ..default()
@@ -17,6 +17,7 @@ use test_game::LOOT_DROPS_Z;
 use crate::{
     characters::components::{CurrentXP, Player, XpPickUpRadius},
     cleanup,
+    mechanics::spatial::PickupGrid,
     sprites::{Item, SpriteKind},
     MovementSpeed,
 };
@@ -72,10 +73,14 @@ pub(super) fn animate_sprite(
 pub fn xp_orbs_collision(
     mut commands: Commands,
     mut player_query: Query<(&Transform, &mut CurrentXP), With<Player>>,
-    mut xp_query: Query<(&Transform, &XP, Entity), With<XP>>,
+    xp_query: Query<(&Transform, &XP, Entity), With<XP>>,
+    pickup_grid: Res<PickupGrid>,
 ) {
     let (player_transform, mut current_xp) = player_query.single_mut().expect("err");
-    for (xp_transform, xp, entity) in xp_query.iter_mut() {
+    for entry in pickup_grid.query(player_transform.translation.xy(), 10.0) {
+        let Ok((xp_transform, xp, entity)) = xp_query.get(entry.item) else {
+            continue;
+        };
         if is_collision(
             player_transform.translation.xy(),
             xp_transform.translation.xy(),
@@ -94,9 +99,13 @@ pub fn xp_orbs_collision(
 pub fn activate_xp_orb_movement(
     mut player_query: Query<(&Transform, &XpPickUpRadius), With<Player>>,
     mut xp_query: Query<(&Transform, &mut MagnetActive), (With<XP>, Without<Player>)>,
+    pickup_grid: Res<PickupGrid>,
 ) {
     let (player_trasnform, pick_up_radius) = player_query.single_mut().expect("err");
-    for (xp_transform, mut active) in xp_query.iter_mut() {
+    for entry in pickup_grid.query(player_trasnform.translation.xy(), **pick_up_radius) {
+        let Ok((xp_transform, mut active)) = xp_query.get_mut(entry.item) else {
+            continue;
+        };
         if is_collision(
             player_trasnform.translation.xy(),
             xp_transform.translation.xy(),
@@ -15,9 +15,21 @@ use std::time::Duration;
 use bevy::camera::{Camera2d, OrthographicProjection, Projection};
 use bevy::winit::WINIT_WINDOWS;
 use bevy::{prelude::*, window::PrimaryWindow};
-use mechanics::cooldown::InGameTime;
+use characters::{
+    bosses::boss::SelectedBoss,
+    enemy_definitions::EnemyDefinitions,
+    wave_director::{WaveDirector, WaveTimeline},
+};
+use mechanics::{
+    cooldown::InGameTime,
+    spatial::{EnemyGrid, PickupGrid},
+};
 use prestige::stats::Stats;
-use skills::skills_tooltips::SkillTooltips;
+use skills::{
+    skill_levels::SkillLevels,
+    skill_offers::{LevelUpOffers, RerollBanishCharges},
+    skills_tooltips::SkillTooltips,
+};
 use sound::{sound_plugin::SoundPlugin, sound_volume::SoundVolume};
 use sprites::add_sprite;
 use start_game::GamePlugin;
@@ -127,8 +139,17 @@ fn setup(mut commands: Commands, window: Query<&mut Window, With<PrimaryWindow>>
     commands.insert_resource(CursorTranslation(Vec2::new(0., 0.)));
     commands.insert_resource(InGameTime::default());
     commands.insert_resource(SkillTooltips::default());
+    commands.insert_resource(SkillLevels::default());
+    commands.insert_resource(LevelUpOffers::default());
+    commands.insert_resource(RerollBanishCharges::default());
     commands.insert_resource(DamageTracker::default());
+    commands.insert_resource(EnemyGrid::default());
+    commands.insert_resource(PickupGrid::default());
     commands.insert_resource(Stats::get_save().unwrap_or_default());
+    commands.insert_resource(EnemyDefinitions::load().unwrap_or_default());
+    commands.insert_resource(WaveTimeline::load().unwrap_or_default());
+    commands.insert_resource(WaveDirector::default());
+    commands.insert_resource(SelectedBoss::default());
     commands.insert_resource(SoundVolume::get_save().unwrap_or_default());
     app_window_config(window);
 }
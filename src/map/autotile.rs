@@ -0,0 +1,294 @@
+use bevy::prelude::*;
+
+use super::map_plugin::TileSheetIndex;
+
+/// Bits of a neighbour mask, set for every neighbour whose ground is drawn over the tile.
+const NORTH: u8 = 1;
+const EAST: u8 = 2;
+const SOUTH: u8 = 4;
+const WEST: u8 = 8;
+const NORTH_EAST: u8 = 16;
+const SOUTH_EAST: u8 = 32;
+const SOUTH_WEST: u8 = 64;
+const NORTH_WEST: u8 = 128;
+const ORTHOGONAL: u8 = NORTH | EAST | SOUTH | WEST;
+
+/// Offset and bit of every neighbour, diagonals also hold the bits of the two orthogonal neighbours next to them.
+const NEIGHBOURS: [(IVec2, u8, u8); 8] = [
+    (IVec2::new(0, 1), NORTH, 0),
+    (IVec2::new(1, 0), EAST, 0),
+    (IVec2::new(0, -1), SOUTH, 0),
+    (IVec2::new(-1, 0), WEST, 0),
+    (IVec2::new(1, 1), NORTH_EAST, NORTH | EAST),
+    (IVec2::new(1, -1), SOUTH_EAST, SOUTH | EAST),
+    (IVec2::new(-1, -1), SOUTH_WEST, SOUTH | WEST),
+    (IVec2::new(-1, 1), NORTH_WEST, NORTH | WEST),
+];
+
+/// Grass edge tile for every neighbour mask the tile sheet has art for,
+/// the tile shows grass on the sides and in the corners whose bits are set.
+const EDGE_TILES: [(u8, TileSheetIndex); 30] = [
+    // Straight edges.
+    (NORTH, TileSheetIndex::GrassEdgeNorth),
+    (EAST, TileSheetIndex::GrassEdgeEast),
+    (SOUTH, TileSheetIndex::GrassEdgeSouth),
+    (WEST, TileSheetIndex::GrassEdgeWest),
+    // Inner corners, where two edges meet.
+    (NORTH | EAST, TileSheetIndex::GrassEdgeNorthEast),
+    (SOUTH | EAST, TileSheetIndex::GrassEdgeSouthEast),
+    (SOUTH | WEST, TileSheetIndex::GrassEdgeSouthWest),
+    (NORTH | WEST, TileSheetIndex::GrassEdgeNorthWest),
+    // Strips and their ends.
+    (NORTH | SOUTH, TileSheetIndex::GrassEdgeNorthSouth),
+    (EAST | WEST, TileSheetIndex::GrassEdgeEastWest),
+    (NORTH | EAST | WEST, TileSheetIndex::GrassEdgeNorthEastWest),
+    (
+        NORTH | SOUTH | EAST,
+        TileSheetIndex::GrassEdgeNorthSouthEast,
+    ),
+    (SOUTH | EAST | WEST, TileSheetIndex::GrassEdgeSouthEastWest),
+    (
+        NORTH | SOUTH | WEST,
+        TileSheetIndex::GrassEdgeNorthSouthWest,
+    ),
+    (ORTHOGONAL, TileSheetIndex::GrassEdgeAll),
+    // Outer corners, where only the diagonal neighbour is grass.
+    (NORTH_EAST, TileSheetIndex::GrassCornerNorthEast),
+    (SOUTH_EAST, TileSheetIndex::GrassCornerSouthEast),
+    (SOUTH_WEST, TileSheetIndex::GrassCornerSouthWest),
+    (NORTH_WEST, TileSheetIndex::GrassCornerNorthWest),
+    (
+        NORTH_WEST | SOUTH_EAST,
+        TileSheetIndex::GrassCornerNorthWestSouthEast,
+    ),
+    (
+        NORTH_EAST | SOUTH_WEST,
+        TileSheetIndex::GrassCornerNorthEastSouthWest,
+    ),
+    (
+        NORTH_EAST | SOUTH_EAST | SOUTH_WEST | NORTH_WEST,
+        TileSheetIndex::GrassCornerAll,
+    ),
+    // Edges with outer corners on the other side.
+    (
+        NORTH | SOUTH_EAST | SOUTH_WEST,
+        TileSheetIndex::GrassEdgeNorthCornerSouth,
+    ),
+    (
+        EAST | NORTH_WEST | SOUTH_WEST,
+        TileSheetIndex::GrassEdgeEastCornerWest,
+    ),
+    (
+        SOUTH | NORTH_EAST | NORTH_WEST,
+        TileSheetIndex::GrassEdgeSouthCornerNorth,
+    ),
+    (
+        WEST | NORTH_EAST | SOUTH_EAST,
+        TileSheetIndex::GrassEdgeWestCornerEast,
+    ),
+    (
+        NORTH | EAST | SOUTH_WEST,
+        TileSheetIndex::GrassEdgeNorthEastCornerSouthWest,
+    ),
+    (
+        SOUTH | EAST | NORTH_WEST,
+        TileSheetIndex::GrassEdgeSouthEastCornerNorthWest,
+    ),
+    (
+        SOUTH | WEST | NORTH_EAST,
+        TileSheetIndex::GrassEdgeSouthWestCornerNorthEast,
+    ),
+    (
+        NORTH | WEST | SOUTH_EAST,
+        TileSheetIndex::GrassEdgeNorthWestCornerSouthEast,
+    ),
+];
+
+/// Edge tile for a neighbour mask. Masks without art fall back to the tile with the same edges
+/// and as many of the corners as possible, so that grass is never drawn where there is none.
+fn edge_tile_for(mask: u8) -> Option<TileSheetIndex> {
+    EDGE_TILES
+        .into_iter()
+        .filter(|&(tile_mask, _)| {
+            tile_mask & ORTHOGONAL == mask & ORTHOGONAL && tile_mask & !mask == 0
+        })
+        .max_by_key(|(tile_mask, _)| tile_mask.count_ones())
+        .map(|(_, kind)| kind)
+}
+
+/// Grass is drawn over sand and dirt. The tile sheet only has grass edges,
+/// so sand and dirt share a priority and their borders with each other get no edges.
+fn priority(ground: TileSheetIndex) -> u8 {
+    match ground {
+        TileSheetIndex::MiddleGrassTile => 1,
+        _ => 0,
+    }
+}
+
+/// The ground of each tile of a rectangle, sampled once so that autotiling
+/// doesn't have to sample the ground noise of every neighbour again.
+pub struct GroundGrid {
+    origin: IVec2,
+    size: IVec2,
+    ground: Vec<TileSheetIndex>,
+}
+
+impl GroundGrid {
+    /// Samples `size` tiles starting at tile `origin`.
+    pub fn sample(origin: IVec2, size: IVec2, ground: impl Fn(IVec2) -> TileSheetIndex) -> Self {
+        let ground = (0..size.y)
+            .flat_map(|y| (0..size.x).map(move |x| origin + IVec2::new(x, y)))
+            .map(ground)
+            .collect();
+        Self {
+            origin,
+            size,
+            ground,
+        }
+    }
+
+    /// Tiles outside the sampled rectangle are never drawn over their neighbours.
+    fn priority(&self, tile: IVec2) -> Option<u8> {
+        let local = tile - self.origin;
+        let inside = local.x >= 0 && local.y >= 0 && local.x < self.size.x && local.y < self.size.y;
+        inside.then(|| priority(self.ground[(local.y * self.size.x + local.x) as usize]))
+    }
+
+    /// Grass edge of a tile with a neighbour whose ground is drawn over it.
+    /// A diagonal neighbour only gives an outer corner when both orthogonal neighbours next to it
+    /// are clear, otherwise the edge already covers that corner.
+    pub fn edge_tile(&self, tile: IVec2) -> Option<TileSheetIndex> {
+        let priority = self.priority(tile);
+        let mask = NEIGHBOURS
+            .into_iter()
+            .filter(|&(offset, _, _)| self.priority(tile + offset) > priority)
+            .fold(0, |mask, (_, bit, _)| mask | bit);
+        let mask = NEIGHBOURS
+            .into_iter()
+            .filter(|&(_, _, sides)| mask & sides != 0)
+            .fold(mask, |mask, (_, bit, _)| mask & !bit);
+        edge_tile_for(mask)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Edge tile indices of a grid drawn with `g` for grass, `s` for sand and `d` for dirt,
+    /// the first line is the northmost row. Tiles without an edge are shown as `.`.
+    fn snapshot(grid: &str) -> Vec<String> {
+        let rows: Vec<&str> = grid.split_whitespace().collect();
+        let size = IVec2::new(rows[0].len() as i32, rows.len() as i32);
+        let ground = GroundGrid::sample(IVec2::ZERO, size, |tile| {
+            match rows[(size.y - 1 - tile.y) as usize].as_bytes()[tile.x as usize] {
+                b'g' => TileSheetIndex::MiddleGrassTile,
+                b's' => TileSheetIndex::MiddleSandTile,
+                _ => TileSheetIndex::MiddleDirtTile,
+            }
+        });
+        (0..size.y)
+            .rev()
+            .map(|y| {
+                (0..size.x)
+                    .map(|x| match ground.edge_tile(IVec2::new(x, y)) {
+                        Some(kind) => (kind as usize).to_string(),
+                        None => ".".to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn grass_island_gets_edges_and_outer_corners() {
+        let grid = "
+            sss
+            sgs
+            sss
+        ";
+        assert_eq!(
+            snapshot(grid),
+            ["1068 676 1069", "677 . 620", "1124 621 1125"]
+        );
+    }
+
+    #[test]
+    fn sand_hole_gets_every_edge() {
+        let grid = "
+            ggg
+            gsg
+            ggg
+        ";
+        assert_eq!(snapshot(grid), [". . .", ". 567 .", ". . ."]);
+    }
+
+    #[test]
+    fn inner_corner_keeps_the_outer_corner_across_it() {
+        let grid = "
+            ggs
+            gss
+            ssg
+        ";
+        assert_eq!(snapshot(grid), [". . 620", ". 396 676", "621 677 ."]);
+    }
+
+    #[test]
+    fn every_diagonal_gives_a_corner() {
+        let grid = "
+            gsg
+            sss
+            gsg
+        ";
+        assert_eq!(snapshot(grid), [". 455 .", "565 453 565", ". 455 ."]);
+    }
+
+    #[test]
+    fn opposite_diagonals_give_both_corners() {
+        let grid = "
+            gss
+            sss
+            ssg
+        ";
+        assert_eq!(snapshot(grid), [". 620 .", "621 1070 676", ". 677 ."]);
+    }
+
+    #[test]
+    fn edge_keeps_the_corners_on_the_other_side() {
+        let grid = "
+            ggg
+            sss
+            gsg
+        ";
+        assert_eq!(snapshot(grid), [". . .", "565 397 565", ". 455 ."]);
+    }
+
+    #[test]
+    fn missing_art_drops_the_corner_but_keeps_the_edge() {
+        let grid = "
+            ggg
+            sss
+            ssg
+        ";
+        assert_eq!(snapshot(grid), [". . .", "621 621 565", ". 677 ."]);
+    }
+
+    #[test]
+    fn sand_and_dirt_borders_get_no_edges() {
+        let grid = "
+            ss
+            dd
+        ";
+        assert_eq!(snapshot(grid), [". .", ". ."]);
+    }
+
+    #[test]
+    fn sand_and_dirt_both_get_grass_edges() {
+        let grid = "
+            sd
+            gg
+        ";
+        assert_eq!(snapshot(grid), ["676 676", ". ."]);
+    }
+}
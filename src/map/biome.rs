@@ -0,0 +1,197 @@
+use bevy::prelude::*;
+use noise::{NoiseFn, Perlin};
+
+use super::map_plugin::{world_to_tile, TileSheetIndex};
+
+/// Scale of the climate noise, much larger than the ground noise so that a biome spans several chunks.
+const CLIMATE_SCALE_FACTOR: f64 = 120.0;
+/// Temperature and moisture closer to 0 than this count as a mild climate.
+const MILD_CLIMATE: f64 = 0.15;
+
+/// Region of the world with its own ground, decorations and optionally its own enemies.
+/// The tilesheet has no snow or swamp ground, so the biomes stick to grass, sand and dirt.
+#[derive(serde::Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Biome {
+    Meadow,
+    Forest,
+    Desert,
+    Badlands,
+}
+
+/// A decoration spawns where the absolute value of its Perlin map is above `threshold`,
+/// so a lower threshold gives denser decorations.
+pub struct DecorationDensity {
+    pub kind: TileSheetIndex,
+    pub threshold: f64,
+}
+
+pub struct BiomeDefinition {
+    /// Ground where the absolute value of the ground noise is below 0.5 and where it is above.
+    pub ground: [TileSheetIndex; 2],
+    /// Decorations on each of the two grounds, checked in order.
+    /// Nothing spawns close to the border between the two grounds.
+    pub decorations: [&'static [DecorationDensity]; 2],
+}
+
+static MEADOW: BiomeDefinition = BiomeDefinition {
+    ground: [
+        TileSheetIndex::MiddleGrassTile,
+        TileSheetIndex::MiddleSandTile,
+    ],
+    decorations: [
+        &[
+            DecorationDensity {
+                kind: TileSheetIndex::Tree,
+                threshold: 0.9,
+            },
+            DecorationDensity {
+                kind: TileSheetIndex::Bush,
+                threshold: 0.8,
+            },
+        ],
+        &[
+            DecorationDensity {
+                kind: TileSheetIndex::Rock,
+                threshold: 0.9,
+            },
+            DecorationDensity {
+                kind: TileSheetIndex::Cactus,
+                threshold: 0.8,
+            },
+        ],
+    ],
+};
+
+static FOREST: BiomeDefinition = BiomeDefinition {
+    ground: [
+        TileSheetIndex::MiddleGrassTile,
+        TileSheetIndex::MiddleDirtTile,
+    ],
+    decorations: [
+        &[
+            DecorationDensity {
+                kind: TileSheetIndex::Tree,
+                threshold: 0.75,
+            },
+            DecorationDensity {
+                kind: TileSheetIndex::Bush,
+                threshold: 0.7,
+            },
+        ],
+        &[
+            DecorationDensity {
+                kind: TileSheetIndex::Bush,
+                threshold: 0.8,
+            },
+            DecorationDensity {
+                kind: TileSheetIndex::Rock,
+                threshold: 0.9,
+            },
+        ],
+    ],
+};
+
+static DESERT: BiomeDefinition = BiomeDefinition {
+    ground: [
+        TileSheetIndex::MiddleSandTile,
+        TileSheetIndex::MiddleDirtTile,
+    ],
+    decorations: [
+        &[
+            DecorationDensity {
+                kind: TileSheetIndex::Cactus,
+                threshold: 0.8,
+            },
+            DecorationDensity {
+                kind: TileSheetIndex::Rock,
+                threshold: 0.9,
+            },
+        ],
+        &[DecorationDensity {
+            kind: TileSheetIndex::Rock,
+            threshold: 0.85,
+        }],
+    ],
+};
+
+static BADLANDS: BiomeDefinition = BiomeDefinition {
+    ground: [
+        TileSheetIndex::MiddleDirtTile,
+        TileSheetIndex::MiddleSandTile,
+    ],
+    decorations: [
+        &[
+            DecorationDensity {
+                kind: TileSheetIndex::Rock,
+                threshold: 0.8,
+            },
+            DecorationDensity {
+                kind: TileSheetIndex::Bush,
+                threshold: 0.9,
+            },
+        ],
+        &[
+            DecorationDensity {
+                kind: TileSheetIndex::Rock,
+                threshold: 0.8,
+            },
+            DecorationDensity {
+                kind: TileSheetIndex::Cactus,
+                threshold: 0.9,
+            },
+        ],
+    ],
+};
+
+impl Biome {
+    pub fn definition(&self) -> &'static BiomeDefinition {
+        match self {
+            Biome::Meadow => &MEADOW,
+            Biome::Forest => &FOREST,
+            Biome::Desert => &DESERT,
+            Biome::Badlands => &BADLANDS,
+        }
+    }
+}
+
+/// Low-frequency temperature and moisture noise that decides the [Biome] of every tile,
+/// part of the [super::map_plugin::MapNoise].
+#[derive(Clone)]
+pub struct BiomeMap {
+    temperature: Perlin,
+    moisture: Perlin,
+}
+
+impl BiomeMap {
+    pub fn new(seed: u32) -> Self {
+        Self {
+            temperature: Perlin::new(seed.wrapping_add(5)),
+            moisture: Perlin::new(seed.wrapping_add(6)),
+        }
+    }
+
+    /// The biome at the world position `pos`.
+    pub fn biome_at(&self, pos: Vec2) -> Biome {
+        self.biome_of_tile(world_to_tile(pos))
+    }
+
+    /// Mild climates are meadows, otherwise hot and dry is desert, hot and wet is meadow,
+    /// cold and wet is forest and cold and dry is badlands.
+    pub fn biome_of_tile(&self, tile: IVec2) -> Biome {
+        let climate_pos = [
+            tile.x as f64 / CLIMATE_SCALE_FACTOR,
+            tile.y as f64 / CLIMATE_SCALE_FACTOR,
+        ];
+        let temperature = self.temperature.get(climate_pos);
+        let moisture = self.moisture.get(climate_pos);
+        if temperature.abs() < MILD_CLIMATE && moisture.abs() < MILD_CLIMATE {
+            return Biome::Meadow;
+        }
+        match (temperature > 0., moisture > 0.) {
+            (true, false) => Biome::Desert,
+            (true, true) => Biome::Meadow,
+            (false, true) => Biome::Forest,
+            (false, false) => Biome::Badlands,
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use bevy::{platform::collections::HashSet, prelude::*};
+
+use super::map_plugin::{tile_to_world, world_to_tile};
+
+/// Half the side length of the square a solid tile blocks, a bit smaller than the tile
+/// so that entities can squeeze past diagonal neighbours.
+const SOLID_HALF_EXTENT: f32 = 12.;
+/// How many rings of tiles around a blocked spawn point are searched for a free spot.
+const MAX_SPAWN_NUDGE_TILES: i32 = 8;
+
+/// What happens when an entity with a [MapCollider] runs into a solid tile.
+#[derive(Clone, Copy)]
+pub enum CollisionResponse {
+    /// Keeps moving along the obstacle on the axis that is not blocked.
+    Slide,
+    /// Reflects the [crate::Heading] off the obstacle.
+    Bounce,
+    /// The entity is despawned.
+    Destroy,
+}
+
+/// Entities moving by their [crate::Heading] with this component collide with the [CollisionGrid].
+#[derive(Component, Clone, Copy)]
+pub struct MapCollider {
+    pub radius: f32,
+    pub response: CollisionResponse,
+}
+
+/// Tiles that block movement, built from the same Perlin maps as the decoration layer.
+/// Only tiles of the chunks around the camera are known, everything else is passable.
+/// A chunk is added as soon as it is requested, so the grid doesn't depend on when its tiles finish generating.
+#[derive(Resource, Default)]
+pub struct CollisionGrid {
+    solid: HashSet<IVec2>,
+}
+
+impl CollisionGrid {
+    pub(super) fn set_solid(&mut self, tile: IVec2) {
+        self.solid.insert(tile);
+    }
+
+    pub(super) fn remove_solid(&mut self, tile: IVec2) {
+        self.solid.remove(&tile);
+    }
+
+    pub fn is_solid(&self, tile: IVec2) -> bool {
+        self.solid.contains(&tile)
+    }
+
+    /// Whether any tile within `distance` tiles of `tile` is solid.
+    pub fn is_near_solid(&self, tile: IVec2, distance: i32) -> bool {
+        (-distance..=distance)
+            .flat_map(|x| (-distance..=distance).map(move |y| IVec2::new(x, y)))
+            .any(|offset| self.is_solid(tile + offset))
+    }
+
+    /// Whether a circle at `pos` with `radius` overlaps a solid tile.
+    pub fn is_blocked(&self, pos: Vec2, radius: f32) -> bool {
+        let reach = Vec2::splat(radius + SOLID_HALF_EXTENT);
+        let min = world_to_tile(pos - reach);
+        let max = world_to_tile(pos + reach);
+        (min.x..=max.x)
+            .flat_map(|x| (min.y..=max.y).map(move |y| IVec2::new(x, y)))
+            .filter(|tile| self.solid.contains(tile))
+            .any(|tile| {
+                let center = tile_to_world(tile);
+                let closest = pos.clamp(
+                    center - Vec2::splat(SOLID_HALF_EXTENT),
+                    center + Vec2::splat(SOLID_HALF_EXTENT),
+                );
+                closest.distance_squared(pos) < radius * radius
+            })
+    }
+
+    /// `pos` if a circle with `radius` fits there without overlapping a solid tile,
+    /// otherwise the closest tile center where it fits, searching up to [MAX_SPAWN_NUDGE_TILES] tiles away.
+    pub fn nearest_free(&self, pos: Vec2, radius: f32) -> Vec2 {
+        if !self.is_blocked(pos, radius) {
+            return pos;
+        }
+        let tile = world_to_tile(pos);
+        (1..=MAX_SPAWN_NUDGE_TILES)
+            .find_map(|ring| {
+                (-ring..=ring)
+                    .flat_map(|x| (-ring..=ring).map(move |y| IVec2::new(x, y)))
+                    .filter(|offset| offset.x.abs() == ring || offset.y.abs() == ring)
+                    .map(|offset| tile_to_world(tile + offset))
+                    .filter(|center| !self.is_blocked(*center, radius))
+                    .min_by(|a, b| a.distance_squared(pos).total_cmp(&b.distance_squared(pos)))
+            })
+            .unwrap_or(pos)
+    }
+
+    /// Where an entity moving from `from` to `to` ends up when it slides along obstacles.
+    /// Entities that are already stuck in a solid tile can move freely, so they are able to get out.
+    pub fn slide(&self, from: Vec2, to: Vec2, radius: f32) -> Vec2 {
+        if !self.is_blocked(to, radius) || self.is_blocked(from, radius) {
+            return to;
+        }
+        let x_only = Vec2::new(to.x, from.y);
+        if !self.is_blocked(x_only, radius) {
+            return x_only;
+        }
+        let y_only = Vec2::new(from.x, to.y);
+        if !self.is_blocked(y_only, radius) {
+            return y_only;
+        }
+        from
+    }
+
+    /// Where an entity moving from `from` to `to` ends up when it bounces off obstacles,
+    /// together with the factors its heading has to be multiplied by.
+    pub fn bounce(&self, from: Vec2, to: Vec2, radius: f32) -> (Vec2, Vec2) {
+        if !self.is_blocked(to, radius) || self.is_blocked(from, radius) {
+            return (to, Vec2::ONE);
+        }
+        let blocked_x = self.is_blocked(Vec2::new(to.x, from.y), radius);
+        let blocked_y = self.is_blocked(Vec2::new(from.x, to.y), radius);
+        let reflection = match (blocked_x, blocked_y) {
+            (true, false) => Vec2::new(-1., 1.),
+            (false, true) => Vec2::new(1., -1.),
+            _ => Vec2::NEG_ONE,
+        };
+        (from, reflection)
+    }
+}
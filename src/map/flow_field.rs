@@ -0,0 +1,246 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::characters::components::Player;
+
+use super::{collision::CollisionGrid, map_plugin::world_to_tile};
+
+/// Side length in tiles of the square around the player that the [FlowField] covers.
+const FLOW_FIELD_SIZE: i32 = 64;
+
+/// Steps to the eight neighbours of a tile, orthogonal ones first so they win ties.
+const NEIGHBOURS: [IVec2; 8] = [
+    IVec2::new(1, 0),
+    IVec2::new(-1, 0),
+    IVec2::new(0, 1),
+    IVec2::new(0, -1),
+    IVec2::new(1, 1),
+    IVec2::new(1, -1),
+    IVec2::new(-1, 1),
+    IVec2::new(-1, -1),
+];
+
+/// Enemies with this component ignore the [FlowField] and always walk straight at their target.
+#[derive(Component)]
+pub struct IgnoresFlowField;
+
+/// Number of steps from every tile around the player to the tile of the player, walking around solid tiles.
+#[derive(Resource, Default)]
+pub struct FlowField {
+    origin: IVec2,
+    size: IVec2,
+    target: Option<IVec2>,
+    /// `u32::MAX` for tiles that can not reach the target.
+    distances: Vec<u32>,
+}
+
+impl FlowField {
+    /// Breadth-first search from `target` over a grid of `size` tiles, starting at tile `origin`.
+    /// Diagonal steps are not allowed to cut the corner of a solid tile.
+    pub fn compute(
+        origin: IVec2,
+        size: IVec2,
+        target: IVec2,
+        is_solid: impl Fn(IVec2) -> bool,
+    ) -> Self {
+        let mut field = Self {
+            origin,
+            size,
+            target: Some(target),
+            distances: vec![u32::MAX; (size.x * size.y).max(0) as usize],
+        };
+        let Some(start) = field.index(target) else {
+            return field;
+        };
+        field.distances[start] = 0;
+        let mut queue = VecDeque::from([target]);
+        while let Some(tile) = queue.pop_front() {
+            let distance = field.distance(tile).expect("queued tiles are reachable");
+            for step in NEIGHBOURS {
+                let next = tile + step;
+                let Some(index) = field.index(next) else {
+                    continue;
+                };
+                let cuts_corner = step.x != 0
+                    && step.y != 0
+                    && (is_solid(tile + IVec2::new(step.x, 0))
+                        || is_solid(tile + IVec2::new(0, step.y)));
+                if field.distances[index] != u32::MAX || is_solid(next) || cuts_corner {
+                    continue;
+                }
+                field.distances[index] = distance + 1;
+                queue.push_back(next);
+            }
+        }
+        field
+    }
+
+    pub fn target(&self) -> Option<IVec2> {
+        self.target
+    }
+
+    /// Steps from `tile` to the target, `None` outside the grid or if the target can not be reached.
+    pub fn distance(&self, tile: IVec2) -> Option<u32> {
+        self.index(tile)
+            .map(|index| self.distances[index])
+            .filter(|&distance| distance != u32::MAX)
+    }
+
+    /// Direction of the next step from `tile` towards the target.
+    /// `None` on the target itself and on tiles without a path, there the straight line has to do.
+    pub fn direction(&self, tile: IVec2) -> Option<Vec2> {
+        let distance = self.distance(tile).filter(|&distance| distance > 0)?;
+        NEIGHBOURS
+            .into_iter()
+            .filter(|step| {
+                step.x == 0
+                    || step.y == 0
+                    || (self.distance(tile + IVec2::new(step.x, 0)).is_some()
+                        && self.distance(tile + IVec2::new(0, step.y)).is_some())
+            })
+            .find(|&step| self.distance(tile + step) == Some(distance - 1))
+            .map(|step| step.as_vec2().normalize())
+    }
+
+    fn index(&self, tile: IVec2) -> Option<usize> {
+        let local = tile - self.origin;
+        let inside = local.x >= 0 && local.y >= 0 && local.x < self.size.x && local.y < self.size.y;
+        inside.then(|| (local.y * self.size.x + local.x) as usize)
+    }
+}
+
+/// Recomputes the [FlowField] around the player whenever they enter another tile
+/// or chunks are loaded or unloaded.
+pub fn update_flow_field(
+    mut flow_field: ResMut<FlowField>,
+    collision_grid: Res<CollisionGrid>,
+    player: Query<&Transform, With<Player>>,
+) {
+    let player_tile = world_to_tile(player.single().expect("Err").translation.xy());
+    if flow_field.target() == Some(player_tile) && !collision_grid.is_changed() {
+        return;
+    }
+    *flow_field = FlowField::compute(
+        player_tile - IVec2::splat(FLOW_FIELD_SIZE / 2),
+        IVec2::splat(FLOW_FIELD_SIZE),
+        player_tile,
+        |tile| collision_grid.is_solid(tile),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    /// Flow field over a grid drawn with `#` for solid and `.` for free tiles, line `y` is row `y`.
+    fn flow_field(grid: &str, target: IVec2) -> (FlowField, HashSet<IVec2>) {
+        let rows: Vec<&str> = grid.split_whitespace().collect();
+        let solid: HashSet<IVec2> = rows
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.chars()
+                    .enumerate()
+                    .filter(|(_, c)| *c == '#')
+                    .map(move |(x, _)| IVec2::new(x as i32, y as i32))
+            })
+            .collect();
+        let size = IVec2::new(rows[0].len() as i32, rows.len() as i32);
+        let field = FlowField::compute(IVec2::ZERO, size, target, |tile| solid.contains(&tile));
+        (field, solid)
+    }
+
+    /// Follows the directions of `field` from `start` and returns every tile on the way to the target.
+    fn walk(field: &FlowField, start: IVec2) -> Vec<IVec2> {
+        let mut path = vec![start];
+        let mut tile = start;
+        while let Some(direction) = field.direction(tile) {
+            tile += direction.round().as_ivec2();
+            path.push(tile);
+            assert!(path.len() < 100, "the path never reaches the target");
+        }
+        path
+    }
+
+    #[test]
+    fn path_goes_around_a_wall() {
+        let grid = "
+            ..#..
+            ..#..
+            ..#..
+            ..#..
+            .....
+        ";
+        let target = IVec2::new(4, 0);
+        let (field, solid) = flow_field(grid, target);
+        let path = walk(&field, IVec2::ZERO);
+        assert_eq!(path.last(), Some(&target));
+        assert!(path.iter().all(|tile| !solid.contains(tile)));
+        assert!(path.iter().any(|tile| tile.y == 4));
+        assert_eq!(field.distance(IVec2::ZERO), Some(path.len() as u32 - 1));
+    }
+
+    #[test]
+    fn diagonal_steps_do_not_cut_corners() {
+        let grid = "
+            ..
+            #.
+        ";
+        let (field, _) = flow_field(grid, IVec2::new(1, 1));
+        assert_eq!(field.distance(IVec2::ZERO), Some(2));
+        assert_eq!(field.direction(IVec2::ZERO), Some(Vec2::X));
+
+        let grid = "
+            .#.
+            #..
+            ...
+        ";
+        let (field, _) = flow_field(grid, IVec2::new(2, 2));
+        assert_eq!(field.distance(IVec2::ZERO), None);
+        assert_eq!(field.direction(IVec2::ZERO), None);
+    }
+
+    #[test]
+    fn enclosed_pocket_is_unreachable() {
+        let grid = "
+            .....
+            .###.
+            .#.#.
+            .###.
+            .....
+        ";
+        let (field, _) = flow_field(grid, IVec2::ZERO);
+        assert_eq!(field.distance(IVec2::new(2, 2)), None);
+        assert_eq!(field.direction(IVec2::new(2, 2)), None);
+        assert_eq!(field.distance(IVec2::new(1, 1)), None);
+        assert_eq!(field.distance(IVec2::new(4, 4)), Some(8));
+    }
+
+    #[test]
+    fn target_on_the_edge_of_the_grid() {
+        let grid = "
+            ....
+            ....
+            ....
+            ....
+        ";
+        let target = IVec2::new(3, 0);
+        let (field, _) = flow_field(grid, target);
+        assert_eq!(field.distance(target), Some(0));
+        assert_eq!(field.direction(target), None);
+        assert_eq!(field.distance(IVec2::new(0, 3)), Some(3));
+        assert_eq!(
+            field.direction(IVec2::new(0, 3)),
+            Some(Vec2::new(1., -1.).normalize())
+        );
+        assert_eq!(walk(&field, IVec2::new(0, 3)).last(), Some(&target));
+        assert_eq!(field.distance(IVec2::new(4, 0)), None);
+
+        let (field, _) = flow_field(grid, IVec2::new(4, 0));
+        assert_eq!(field.distance(IVec2::ZERO), None);
+        assert_eq!(field.direction(IVec2::ZERO), None);
+    }
+}
@@ -1,18 +1,54 @@
-use bevy::prelude::*;
+use bevy::{
+    platform::collections::HashMap,
+    prelude::*,
+    tasks::{block_on, AsyncComputeTaskPool, Task},
+};
+use bevy_ecs_tilemap::prelude::*;
 use noise::{NoiseFn, Perlin};
-use rand::Rng;
 use test_game::{TILE_LAYER_1_Z, TILE_LAYER_2_Z, TILE_LAYER_3_Z};
 
-const MAP_SIZE: (usize, usize) = (128, 128);
+use crate::{
+    cleanup,
+    tools::rng::{RngStream, RunSeed},
+    AppState, GameState, MyGameCamera,
+};
+
+use super::{
+    autotile::GroundGrid,
+    biome::{BiomeDefinition, BiomeMap, DecorationDensity},
+    collision::CollisionGrid,
+    flow_field::{update_flow_field, FlowField},
+};
+
 const TILE_SIZE: Vec2 = Vec2::new(32.0, 32.0);
-const GRID_COL: u32 = 56;
-const GRID_ROW: u32 = 24;
-const START_X: f32 = -(MAP_SIZE.0 as f32 * TILE_SIZE.x) / 2.0;
-const START_Y: f32 = -(MAP_SIZE.1 as f32 * TILE_SIZE.y) / 2.0;
 const PERLIN_SCALE_FACTOR: f64 = 15.0;
+/// Side length of a chunk in tiles.
+const CHUNK_SIZE: i32 = 16;
+/// Chunks this many chunks or closer to the chunk of the camera are generated.
+const CHUNK_LOAD_RADIUS: i32 = 2;
+/// Chunks further away than this from the chunk of the camera are despawned,
+/// larger than [CHUNK_LOAD_RADIUS] so that walking along a chunk border doesn't regenerate chunks.
+const CHUNK_UNLOAD_RADIUS: i32 = 3;
+/// Generated chunks are spawned over several frames, so that a burst of finished chunks doesn't stall a frame.
+const CHUNKS_SPAWNED_PER_FRAME: usize = 4;
+
+/// The tile whose center is closest to `pos`.
+pub fn world_to_tile(pos: Vec2) -> IVec2 {
+    (pos / TILE_SIZE).round().as_ivec2()
+}
+
+/// World position of the center of `tile`.
+pub fn tile_to_world(tile: IVec2) -> Vec2 {
+    tile.as_vec2() * TILE_SIZE
+}
+
+/// The chunk containing `tile`.
+fn tile_to_chunk(tile: IVec2) -> IVec2 {
+    tile.div_euclid(IVec2::splat(CHUNK_SIZE))
+}
 
 #[repr(usize)]
-#[derive(PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 /// A Unit-only enum represinting the different indexes (usize) used in tilesheet.
 /// An enum is used to more easily clearify and understand what indexes are represent what in the tilesheet so that we dont have to remember each index manually.
 /// Consider changing this into e.g consts in lib.rs or something complety different.
@@ -28,29 +64,60 @@ const PERLIN_SCALE_FACTOR: f64 = 15.0;
 pub enum TileSheetIndex {
     MiddleGrassTile = 113,
     MiddleSandTile = 117,
+    MiddleDirtTile = 121,
+    GrassEdgeNorthWestCornerSouthEast = 396,
+    GrassEdgeNorthCornerSouth = 397,
+    GrassEdgeNorthEastCornerSouthWest = 398,
+    GrassEdgeNorthEastWest = 399,
     Cactus = 402,
+    GrassEdgeWestCornerEast = 452,
+    GrassCornerAll = 453,
+    GrassEdgeEastCornerWest = 454,
+    GrassEdgeEastWest = 455,
+    GrassEdgeSouthWestCornerNorthEast = 508,
+    GrassEdgeSouthCornerNorth = 509,
+    GrassEdgeSouthEastCornerNorthWest = 510,
+    GrassEdgeSouthEastWest = 511,
     Rock = 512,
+    GrassEdgeNorthSouthWest = 564,
+    GrassEdgeNorthSouth = 565,
+    GrassEdgeNorthSouthEast = 566,
+    GrassEdgeAll = 567,
+    GrassEdgeWest = 620,
+    GrassEdgeNorth = 621,
+    GrassEdgeNorthWest = 622,
+    GrassEdgeNorthEast = 623,
+    GrassEdgeSouth = 676,
+    GrassEdgeEast = 677,
+    GrassEdgeSouthWest = 678,
+    GrassEdgeSouthEast = 679,
     Tree = 1018,
+    GrassCornerSouthEast = 1068,
+    GrassCornerSouthWest = 1069,
+    GrassCornerNorthWestSouthEast = 1070,
+    GrassCornerNorthEastSouthWest = 1071,
     Bush = 1078,
-    DownGrassTile = 1182,
-    LeftGrassTile = 1183,
-    RightGrassTile = 1238,
-    TopGrassTile = 1239,
+    GrassCornerNorthEast = 1124,
+    GrassCornerNorthWest = 1125,
 }
 
 impl TileSheetIndex {
-    fn depth(&self) -> f32 {
-        match self {
-            TileSheetIndex::MiddleGrassTile | TileSheetIndex::MiddleSandTile => TILE_LAYER_1_Z,
+    fn is_decoration(&self) -> bool {
+        matches!(
+            self,
             TileSheetIndex::Cactus
-            | TileSheetIndex::Rock
-            | TileSheetIndex::Tree
-            | TileSheetIndex::Bush => TILE_LAYER_3_Z,
-            TileSheetIndex::DownGrassTile
-            | TileSheetIndex::LeftGrassTile
-            | TileSheetIndex::RightGrassTile
-            | TileSheetIndex::TopGrassTile => TILE_LAYER_2_Z,
-        }
+                | TileSheetIndex::Rock
+                | TileSheetIndex::Tree
+                | TileSheetIndex::Bush
+        )
+    }
+
+    /// Whether the tile blocks movement, bushes can be walked through.
+    fn is_solid(&self) -> bool {
+        matches!(
+            self,
+            TileSheetIndex::Cactus | TileSheetIndex::Rock | TileSheetIndex::Tree
+        )
     }
 }
 
@@ -58,6 +125,14 @@ impl TileSheetIndex {
 /// Generation seed used strictly for the Perlin grid for map generation.
 pub struct GenerationSeed(u32);
 
+/// Trees, bushes, rocks and cacti on the decoration layer.
+#[derive(Component)]
+pub struct Decoration;
+
+/// Decoration that blocks movement, see [CollisionGrid].
+#[derive(Component)]
+pub struct SolidCollider;
+
 #[derive(Component)]
 pub struct LayerOne;
 
@@ -67,350 +142,441 @@ pub struct LayerTwo;
 #[derive(Component)]
 pub struct LayerThree;
 
+/// Chunks that are currently spawned, by chunk position.
+/// The entity of a chunk is the parent of the tilemaps of its three layers.
+#[derive(Resource, Default)]
+pub struct LoadedChunks(HashMap<IVec2, Entity>);
+
+/// Chunks whose tiles are being generated in the background, by chunk position.
+#[derive(Resource, Default)]
+pub struct GeneratingChunks(HashMap<IVec2, Task<ChunkTiles>>);
+
 pub struct MapPlugin;
 
 impl Plugin for MapPlugin {
     fn build(&self, app: &mut App) {
-        let mut rng = rand::thread_rng();
-        app.insert_resource(GenerationSeed(rng.gen()))
-            .add_systems(Startup, setup_map);
+        app.insert_resource(CollisionGrid::default())
+            .insert_resource(FlowField::default())
+            .insert_resource(LoadedChunks::default())
+            .insert_resource(GeneratingChunks::default())
+            .add_systems(OnEnter(AppState::InGame), seed_map)
+            .add_systems(PreUpdate, update_chunks.run_if(in_state(AppState::InGame)))
+            .add_systems(
+                Update,
+                spawn_generated_chunks.run_if(in_state(AppState::InGame)),
+            )
+            .add_systems(
+                Update,
+                update_flow_field.run_if(in_state(GameState::Running)),
+            )
+            .add_systems(OnExit(AppState::InGame), reset_map);
     }
 }
 
-pub fn setup_map(
+/// Perlin maps of the biomes, the ground and every kind of decoration, all derived from the [GenerationSeed].
+#[derive(Resource, Clone)]
+pub struct MapNoise {
+    biomes: BiomeMap,
+    ground: Perlin,
+    tree: Perlin,
+    bush: Perlin,
+    rock: Perlin,
+    cactus: Perlin,
+}
+
+impl MapNoise {
+    fn new(seed: u32) -> Self {
+        Self {
+            biomes: BiomeMap::new(seed),
+            ground: generate_noise_map(seed),
+            tree: generate_noise_map(seed.wrapping_add(1)),
+            bush: generate_noise_map(seed.wrapping_add(2)),
+            rock: generate_noise_map(seed.wrapping_add(3)),
+            cactus: generate_noise_map(seed.wrapping_add(4)),
+        }
+    }
+
+    pub fn biomes(&self) -> &BiomeMap {
+        &self.biomes
+    }
+
+    /// Ground tile of `tile`, whether its chunk is loaded or not.
+    pub fn ground(&self, tile: IVec2) -> TileSheetIndex {
+        let biome = self.biomes.biome_of_tile(tile).definition();
+        get_ground_texture_index(biome, self.ground.get(noise_pos(tile)))
+    }
+
+    /// Whether the decoration of `tile` blocks movement, whether its chunk is loaded or not.
+    fn is_solid(&self, tile: IVec2) -> bool {
+        self.decoration(tile).is_some_and(|kind| kind.is_solid())
+    }
+
+    fn decoration(&self, tile: IVec2) -> Option<TileSheetIndex> {
+        let noise_pos = noise_pos(tile);
+        let biome = self.biomes.biome_of_tile(tile).definition();
+        get_decorations(biome, self.ground.get(noise_pos))
+            .iter()
+            .find(|decoration| {
+                self.decoration_map(decoration.kind).get(noise_pos).abs() > decoration.threshold
+            })
+            .map(|decoration| decoration.kind)
+    }
+
+    fn decoration_map(&self, kind: TileSheetIndex) -> &Perlin {
+        match kind {
+            TileSheetIndex::Tree => &self.tree,
+            TileSheetIndex::Bush => &self.bush,
+            TileSheetIndex::Rock => &self.rock,
+            TileSheetIndex::Cactus => &self.cactus,
+            _ => unreachable!("only decorations have a noise map"),
+        }
+    }
+}
+
+fn noise_pos(tile: IVec2) -> [f64; 2] {
+    [
+        tile.x as f64 / PERLIN_SCALE_FACTOR,
+        tile.y as f64 / PERLIN_SCALE_FACTOR,
+    ]
+}
+
+/// Derives the [GenerationSeed] and the [MapNoise] from the [RunSeed].
+fn seed_map(mut commands: Commands, run_seed: Res<RunSeed>) {
+    let seed = run_seed.derive(RngStream::Map) as u32;
+    commands.insert_resource(GenerationSeed(seed));
+    commands.insert_resource(MapNoise::new(seed));
+}
+
+/// Starts generating the chunks around the camera in the background and despawns the ones that are far away,
+/// so the world looks unbounded wherever the player walks.
+/// Every chunk is generated again once the [GenerationSeed] changes.
+///
+/// The solid tiles of a chunk go into the [CollisionGrid] right away instead of when its background task finishes,
+/// so collisions only depend on where the camera is and not on how fast the chunks are generated.
+/// This runs in [PreUpdate] so that no gameplay system sees the grid before the chunks of the frame are in it.
+fn update_chunks(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
     seed: Res<GenerationSeed>,
+    noise: Res<MapNoise>,
+    mut loaded_chunks: ResMut<LoadedChunks>,
+    mut generating_chunks: ResMut<GeneratingChunks>,
+    mut collision_grid: ResMut<CollisionGrid>,
+    camera: Query<&Transform, With<MyGameCamera>>,
 ) {
-    let map = generate_noise_map(seed.0);
-    spawn_ground_layer(
-        &mut commands,
-        &asset_server,
-        &mut texture_atlas_layouts,
-        &map,
-    );
-    spawn_decoration_layer(
-        &mut commands,
-        &asset_server,
-        &mut texture_atlas_layouts,
-        &map,
-        seed,
-    );
-    spawn_rounded_edges_layer(
-        &mut commands,
-        &asset_server,
-        &mut texture_atlas_layouts,
-        &map,
-    );
+    let Ok(camera) = camera.single() else {
+        return;
+    };
+    let center = tile_to_chunk(world_to_tile(camera.translation.xy()));
+    let is_within = |chunk: IVec2, radius: i32| (chunk - center).abs().max_element() <= radius;
+
+    let stale_chunks: Vec<IVec2> = loaded_chunks
+        .0
+        .keys()
+        .chain(generating_chunks.0.keys())
+        .copied()
+        .filter(|&chunk| seed.is_changed() || !is_within(chunk, CHUNK_UNLOAD_RADIUS))
+        .collect();
+    for chunk in stale_chunks {
+        if let Some(entity) = loaded_chunks.0.remove(&chunk) {
+            commands.entity(entity).despawn();
+        }
+        // Dropping a task cancels it.
+        generating_chunks.0.remove(&chunk);
+        for tile in chunk_tiles(chunk) {
+            collision_grid.remove_solid(tile);
+        }
+    }
+
+    let missing_chunks: Vec<IVec2> = chunks_around(center)
+        .filter(|chunk| {
+            !loaded_chunks.0.contains_key(chunk) && !generating_chunks.0.contains_key(chunk)
+        })
+        .collect();
+    if missing_chunks.is_empty() {
+        return;
+    }
+    let task_pool = AsyncComputeTaskPool::get();
+    for chunk in missing_chunks {
+        for tile in chunk_tiles(chunk).filter(|&tile| noise.is_solid(tile)) {
+            collision_grid.set_solid(tile);
+        }
+        let noise = noise.clone();
+        let task = task_pool.spawn(async move { ChunkTiles::generate(&noise, chunk) });
+        generating_chunks.0.insert(chunk, task);
+    }
 }
 
-fn spawn_decoration_layer(
-    commands: &mut Commands,
-    asset_server: &Res<AssetServer>,
-    texture_atlas_layouts: &mut ResMut<Assets<TextureAtlasLayout>>,
-    map: &Perlin,
-    seed: Res<GenerationSeed>,
+/// Spawns the chunks whose tiles are done generating, at most [CHUNKS_SPAWNED_PER_FRAME] per frame.
+fn spawn_generated_chunks(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut loaded_chunks: ResMut<LoadedChunks>,
+    mut generating_chunks: ResMut<GeneratingChunks>,
 ) {
-    let tree_map = generate_noise_map(seed.0.wrapping_add(1));
-    let bush_map = generate_noise_map(seed.0.wrapping_add(2));
-    let rock_map = generate_noise_map(seed.0.wrapping_add(3));
-    let cactus_map = generate_noise_map(seed.0.wrapping_add(4));
-
+    let finished_chunks: Vec<IVec2> = generating_chunks
+        .0
+        .iter()
+        .filter(|(_, task)| task.is_finished())
+        .map(|(&chunk, _)| chunk)
+        .take(CHUNKS_SPAWNED_PER_FRAME)
+        .collect();
+    if finished_chunks.is_empty() {
+        return;
+    }
     let texture_handle: Handle<Image> = asset_server.load("environment/map_tilesheet.png");
-    let layout = TextureAtlasLayout::from_grid(
-        UVec2::new(TILE_SIZE.x as u32, TILE_SIZE.y as u32),
-        GRID_COL,
-        GRID_ROW,
-        None,
-        None,
-    );
-    let texture_atlas_layout = texture_atlas_layouts.add(layout);
-    commands
-        .spawn(())
-        .insert(Name::new("Map Layer Three"))
-        .insert(LayerThree)
-        .insert(Transform::default())
-        .insert(GlobalTransform::default())
-        .insert(InheritedVisibility::default())
-        .with_children(|child| {
-            for x in 0..MAP_SIZE.0 {
-                for y in 0..MAP_SIZE.1 {
-                    if should_spawn_tree(
-                        map.get([
-                            x as f64 / PERLIN_SCALE_FACTOR,
-                            y as f64 / PERLIN_SCALE_FACTOR,
-                        ]),
-                        tree_map.get([
-                            x as f64 / PERLIN_SCALE_FACTOR,
-                            y as f64 / PERLIN_SCALE_FACTOR,
-                        ]),
-                    ) {
-                        child.spawn(tile(
-                            TileSheetIndex::Tree,
-                            x,
-                            y,
-                            texture_handle.clone(),
-                            texture_atlas_layout.clone(),
-                        ));
-                    } else if should_spawn_bush(
-                        map.get([
-                            x as f64 / PERLIN_SCALE_FACTOR,
-                            y as f64 / PERLIN_SCALE_FACTOR,
-                        ]),
-                        bush_map.get([
-                            x as f64 / PERLIN_SCALE_FACTOR,
-                            y as f64 / PERLIN_SCALE_FACTOR,
-                        ]),
-                    ) {
-                        child.spawn(tile(
-                            TileSheetIndex::Bush,
-                            x,
-                            y,
-                            texture_handle.clone(),
-                            texture_atlas_layout.clone(),
-                        ));
-                    } else if should_spawn_rock(
-                        map.get([
-                            x as f64 / PERLIN_SCALE_FACTOR,
-                            y as f64 / PERLIN_SCALE_FACTOR,
-                        ]),
-                        rock_map.get([
-                            x as f64 / PERLIN_SCALE_FACTOR,
-                            y as f64 / PERLIN_SCALE_FACTOR,
-                        ]),
-                    ) {
-                        child.spawn(tile(
-                            TileSheetIndex::Rock,
-                            x,
-                            y,
-                            texture_handle.clone(),
-                            texture_atlas_layout.clone(),
-                        ));
-                    } else if should_spawn_cactus(
-                        map.get([
-                            x as f64 / PERLIN_SCALE_FACTOR,
-                            y as f64 / PERLIN_SCALE_FACTOR,
-                        ]),
-                        cactus_map.get([
-                            x as f64 / PERLIN_SCALE_FACTOR,
-                            y as f64 / PERLIN_SCALE_FACTOR,
-                        ]),
-                    ) {
-                        child.spawn(tile(
-                            TileSheetIndex::Cactus,
-                            x,
-                            y,
-                            texture_handle.clone(),
-                            texture_atlas_layout.clone(),
-                        ));
-                    }
-                }
-            }
-        });
+    for chunk in finished_chunks {
+        let Some(task) = generating_chunks.0.remove(&chunk) else {
+            continue;
+        };
+        let tiles = block_on(task);
+        let entity = spawn_chunk(&mut commands, texture_handle.clone(), chunk, &tiles);
+        loaded_chunks.0.insert(chunk, entity);
+    }
 }
 
-fn tile(
-    kind: TileSheetIndex,
-    x: usize,
-    y: usize,
-    texture_handle: Handle<Image>,
-    texture_atlas_layout: Handle<TextureAtlasLayout>,
-) -> impl Bundle {
-    let depth = kind.depth();
-    (
-        Sprite {
-            image: texture_handle.clone(),
-            texture_atlas: Some(TextureAtlas {
-                layout: texture_atlas_layout.clone(),
-                index: kind as usize,
-            }),
-            ..default()
-        },
-        Transform::from_translation(Vec3::new(
-            START_X + (x as f32 * TILE_SIZE.x),
-            START_Y + (y as f32 * TILE_SIZE.y),
-            depth,
-        )),
-    )
+/// Forgets the map of the run that just ended, its chunks are despawned with the other [cleanup::ExitGame] entities.
+fn reset_map(
+    mut loaded_chunks: ResMut<LoadedChunks>,
+    mut generating_chunks: ResMut<GeneratingChunks>,
+    mut collision_grid: ResMut<CollisionGrid>,
+    mut flow_field: ResMut<FlowField>,
+) {
+    loaded_chunks.0.clear();
+    generating_chunks.0.clear();
+    *collision_grid = CollisionGrid::default();
+    *flow_field = FlowField::default();
+}
+
+/// Every chunk within [CHUNK_LOAD_RADIUS] of `center`.
+fn chunks_around(center: IVec2) -> impl Iterator<Item = IVec2> {
+    (-CHUNK_LOAD_RADIUS..=CHUNK_LOAD_RADIUS)
+        .flat_map(|x| (-CHUNK_LOAD_RADIUS..=CHUNK_LOAD_RADIUS).map(move |y| IVec2::new(x, y)))
+        .map(move |offset| center + offset)
+}
+
+/// Whether every chunk within [CHUNK_LOAD_RADIUS] of the camera is spawned.
+pub fn chunks_around_camera_loaded(
+    loaded_chunks: Res<LoadedChunks>,
+    camera: Query<&Transform, With<MyGameCamera>>,
+) -> bool {
+    let Ok(camera) = camera.single() else {
+        return false;
+    };
+    chunks_around(tile_to_chunk(world_to_tile(camera.translation.xy())))
+        .all(|chunk| loaded_chunks.0.contains_key(&chunk))
+}
+
+/// Every tile of `chunk`.
+fn chunk_tiles(chunk: IVec2) -> impl Iterator<Item = IVec2> {
+    let origin = chunk * CHUNK_SIZE;
+    (0..CHUNK_SIZE).flat_map(move |x| (0..CHUNK_SIZE).map(move |y| origin + IVec2::new(x, y)))
+}
+
+/// Tiles of the three layers of a chunk, indexed by `y * CHUNK_SIZE + x` within the chunk.
+pub struct ChunkTiles {
+    ground: Vec<Option<TileSheetIndex>>,
+    edges: Vec<Option<TileSheetIndex>>,
+    decorations: Vec<Option<TileSheetIndex>>,
+}
+
+impl ChunkTiles {
+    /// Samples every tile of `chunk`, this is the slow part of generating a chunk.
+    fn generate(noise: &MapNoise, chunk: IVec2) -> Self {
+        let origin = chunk * CHUNK_SIZE;
+        // One tile of border so that the edges of the chunk see their neighbours in the next chunks.
+        let ground =
+            GroundGrid::sample(origin - IVec2::ONE, IVec2::splat(CHUNK_SIZE + 2), |tile| {
+                noise.ground(tile)
+            });
+        let tiles: Vec<IVec2> = (0..CHUNK_SIZE)
+            .flat_map(|y| (0..CHUNK_SIZE).map(move |x| origin + IVec2::new(x, y)))
+            .collect();
+        Self {
+            ground: tiles.iter().map(|&tile| Some(noise.ground(tile))).collect(),
+            edges: tiles.iter().map(|&tile| ground.edge_tile(tile)).collect(),
+            decorations: tiles.iter().map(|&tile| noise.decoration(tile)).collect(),
+        }
+    }
 }
 
-fn spawn_ground_layer(
+fn spawn_chunk(
     commands: &mut Commands,
-    asset_server: &Res<AssetServer>,
-    texture_atlas_layouts: &mut ResMut<Assets<TextureAtlasLayout>>,
-    map: &Perlin,
-) {
-    let texture_handle: Handle<Image> = asset_server.load("environment/map_tilesheet.png");
-    let layout = TextureAtlasLayout::from_grid(
-        UVec2::new(TILE_SIZE.x as u32, TILE_SIZE.y as u32),
-        GRID_COL,
-        GRID_ROW,
-        None,
-        None,
+    texture_handle: Handle<Image>,
+    chunk: IVec2,
+    tiles: &ChunkTiles,
+) -> Entity {
+    let origin = chunk * CHUNK_SIZE;
+    let chunk_entity = commands
+        .spawn((
+            Name::new(format!("Map Chunk {chunk}")),
+            Transform::from_translation(tile_to_world(origin).extend(0.)),
+            Visibility::default(),
+            cleanup::ExitGame,
+        ))
+        .id();
+    spawn_tilemap_layer(
+        commands,
+        chunk_entity,
+        texture_handle.clone(),
+        (Name::new("Map Layer One"), LayerOne),
+        TILE_LAYER_1_Z,
+        &tiles.ground,
     );
-    let texture_atlas_layout = texture_atlas_layouts.add(layout);
-    commands
-        .spawn(())
-        .insert(Name::new("Map Layer One"))
-        .insert(LayerOne)
-        .insert(Transform::default())
-        .insert(GlobalTransform::default())
-        .insert(InheritedVisibility::default())
-        .with_children(|child| {
-            for x in 0..MAP_SIZE.0 {
-                for y in 0..MAP_SIZE.1 {
-                    let tile_sheet_index = get_ground_texture_index(map.get([
-                        x as f64 / PERLIN_SCALE_FACTOR,
-                        y as f64 / PERLIN_SCALE_FACTOR,
-                    ]));
-                    child.spawn(tile(
-                        tile_sheet_index,
-                        x,
-                        y,
-                        texture_handle.clone(),
-                        texture_atlas_layout.clone(),
-                    ));
-                }
-            }
-        });
+    spawn_tilemap_layer(
+        commands,
+        chunk_entity,
+        texture_handle.clone(),
+        (Name::new("Map Layer Two"), LayerTwo),
+        TILE_LAYER_2_Z,
+        &tiles.edges,
+    );
+    spawn_tilemap_layer(
+        commands,
+        chunk_entity,
+        texture_handle,
+        (Name::new("Map Layer Three"), LayerThree),
+        TILE_LAYER_3_Z,
+        &tiles.decorations,
+    );
+    chunk_entity
 }
 
-fn spawn_rounded_edges_layer(
+/// Spawns one layer of a chunk as a tilemap at `depth`, `tiles` holds the tile of every position of the chunk.
+fn spawn_tilemap_layer(
     commands: &mut Commands,
-    asset_server: &Res<AssetServer>,
-    texture_atlas_layouts: &mut ResMut<Assets<TextureAtlasLayout>>,
-    map: &Perlin,
+    chunk_entity: Entity,
+    texture_handle: Handle<Image>,
+    layer: impl Bundle,
+    depth: f32,
+    tiles: &[Option<TileSheetIndex>],
 ) {
-    let texture_handle: Handle<Image> = asset_server.load("environment/map_tilesheet.png");
-    let layout = TextureAtlasLayout::from_grid(
-        UVec2::new(TILE_SIZE.x as u32, TILE_SIZE.y as u32),
-        GRID_COL,
-        GRID_ROW,
-        None,
-        None,
-    );
-    let texture_atlas_layout = texture_atlas_layouts.add(layout);
-
-    commands
-        .spawn(())
-        .insert(Name::new("Map Layer Two"))
-        .insert(LayerTwo)
-        .insert(Transform::default())
-        .insert(GlobalTransform::default())
-        .insert(InheritedVisibility::default())
-        .with_children(|child| {
-            for x in 0..MAP_SIZE.0 {
-                for y in 0..MAP_SIZE.1 {
-                    let tile_sheet_index = get_ground_texture_index(map.get([
-                        x as f64 / PERLIN_SCALE_FACTOR,
-                        y as f64 / PERLIN_SCALE_FACTOR,
-                    ]));
-                    match tile_sheet_index {
-                        TileSheetIndex::MiddleSandTile => {
-                            // Check for bottom
-                            if y < MAP_SIZE.1
-                                && get_ground_texture_index(map.get([
-                                    x as f64 / PERLIN_SCALE_FACTOR,
-                                    (y + 1) as f64 / PERLIN_SCALE_FACTOR,
-                                ])) == TileSheetIndex::MiddleGrassTile
-                            {
-                                child.spawn(tile(
-                                    TileSheetIndex::DownGrassTile,
-                                    x,
-                                    y,
-                                    texture_handle.clone(),
-                                    texture_atlas_layout.clone(),
-                                ));
-                            }
-                            // Check for Top
-                            if y > 0
-                                && get_ground_texture_index(map.get([
-                                    x as f64 / PERLIN_SCALE_FACTOR,
-                                    (y - 1) as f64 / PERLIN_SCALE_FACTOR,
-                                ])) == TileSheetIndex::MiddleGrassTile
-                            {
-                                child.spawn(tile(
-                                    TileSheetIndex::TopGrassTile,
-                                    x,
-                                    y,
-                                    texture_handle.clone(),
-                                    texture_atlas_layout.clone(),
-                                ));
-                            }
-                            // Check for Left
-                            if x < MAP_SIZE.0
-                                && get_ground_texture_index(map.get([
-                                    (x + 1) as f64 / PERLIN_SCALE_FACTOR,
-                                    y as f64 / PERLIN_SCALE_FACTOR,
-                                ])) == TileSheetIndex::MiddleGrassTile
-                            {
-                                child.spawn(tile(
-                                    TileSheetIndex::LeftGrassTile,
-                                    x,
-                                    y,
-                                    texture_handle.clone(),
-                                    texture_atlas_layout.clone(),
-                                ));
-                            }
-                            // Check for Right.
-                            if x > 0
-                                && get_ground_texture_index(map.get([
-                                    (x - 1) as f64 / PERLIN_SCALE_FACTOR,
-                                    y as f64 / PERLIN_SCALE_FACTOR,
-                                ])) == TileSheetIndex::MiddleGrassTile
-                            {
-                                child.spawn(tile(
-                                    TileSheetIndex::RightGrassTile,
-                                    x,
-                                    y,
-                                    texture_handle.clone(),
-                                    texture_atlas_layout.clone(),
-                                ));
-                            }
-                        }
-                        _ => continue,
-                    }
-                }
+    let map_size = TilemapSize {
+        x: CHUNK_SIZE as u32,
+        y: CHUNK_SIZE as u32,
+    };
+    let tile_size = TilemapTileSize {
+        x: TILE_SIZE.x,
+        y: TILE_SIZE.y,
+    };
+    let mut tile_storage = TileStorage::empty(map_size);
+    let tilemap_entity = commands.spawn((layer, ChildOf(chunk_entity))).id();
+    for x in 0..map_size.x {
+        for y in 0..map_size.y {
+            let Some(kind) = tiles[(y * map_size.x + x) as usize] else {
+                continue;
+            };
+            let position = TilePos { x, y };
+            let mut tile = commands.spawn((
+                TileBundle {
+                    position,
+                    tilemap_id: TilemapId(tilemap_entity),
+                    texture_index: TileTextureIndex(kind as u32),
+                    ..default()
+                },
+                ChildOf(tilemap_entity),
+            ));
+            if kind.is_decoration() {
+                tile.insert(Decoration);
             }
-        });
+            if kind.is_solid() {
+                tile.insert(SolidCollider);
+            }
+            tile_storage.set(&position, tile.id());
+        }
+    }
+    commands.entity(tilemap_entity).insert(TilemapBundle {
+        grid_size: tile_size.into(),
+        size: map_size,
+        storage: tile_storage,
+        texture: TilemapTexture::Single(texture_handle),
+        tile_size,
+        transform: Transform::from_xyz(0., 0., depth),
+        ..default()
+    });
 }
+
 fn generate_noise_map(seed: u32) -> Perlin {
     Perlin::new(seed)
 }
 
-fn should_spawn_tree(ground_val: f64, decoration_val: f64) -> bool {
+/// Decorations of the ground at `ground_val`, nothing spawns close to the border between the two grounds.
+fn get_decorations(biome: &BiomeDefinition, ground_val: f64) -> &'static [DecorationDensity] {
     match ground_val.abs() {
-        v if v < 0.4 => matches!(decoration_val.abs(), vd if vd > 0.9),
-        _ => false,
+        v if v < 0.4 => biome.decorations[0],
+        v if v > 0.6 => biome.decorations[1],
+        _ => &[],
     }
 }
 
-fn should_spawn_bush(ground_val: f64, decoration_val: f64) -> bool {
-    match ground_val.abs() {
-        v if v < 0.4 => matches!(decoration_val.abs(), vd if vd > 0.8 && vd < 0.9),
-        _ => false,
+fn get_ground_texture_index(biome: &BiomeDefinition, val: f64) -> TileSheetIndex {
+    match val.abs() {
+        v if v < 0.5 => biome.ground[0],
+        _ => biome.ground[1],
     }
 }
 
-fn should_spawn_rock(ground_val: f64, decoration_val: f64) -> bool {
-    match ground_val.abs() {
-        v if v > 0.6 => matches!(decoration_val.abs(), vd if vd > 0.9),
-        _ => false,
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::RunSystemOnce, tasks::TaskPool};
+
+    use super::*;
+
+    /// A new run with `seed` and the camera at the origin, after the chunks around it are requested
+    /// and before any of them finished generating.
+    fn requested_map(seed: u64) -> World {
+        AsyncComputeTaskPool::get_or_init(TaskPool::new);
+        let mut world = World::new();
+        world.insert_resource(RunSeed(seed));
+        world.insert_resource(CollisionGrid::default());
+        world.insert_resource(LoadedChunks::default());
+        world.insert_resource(GeneratingChunks::default());
+        world.spawn((Transform::default(), MyGameCamera));
+        world.run_system_once(seed_map).expect("seed_map runs");
+        world
+            .run_system_once(update_chunks)
+            .expect("update_chunks runs");
+        world
     }
-}
 
-fn should_spawn_cactus(ground_val: f64, decoration_val: f64) -> bool {
-    match ground_val.abs() {
-        v if v > 0.6 => matches!(decoration_val.abs(), vd if vd > 0.8 && vd < 0.9),
-        _ => false,
+    /// Solid tiles of the [CollisionGrid] around the origin, ordered by x and then y.
+    fn solid_tiles(world: &World) -> Vec<IVec2> {
+        let grid = world.resource::<CollisionGrid>();
+        let reach = (CHUNK_LOAD_RADIUS + 1) * CHUNK_SIZE;
+        (-reach..reach)
+            .flat_map(|x| (-reach..reach).map(move |y| IVec2::new(x, y)))
+            .filter(|&tile| grid.is_solid(tile))
+            .collect()
     }
-}
 
-fn get_ground_texture_index(val: f64) -> TileSheetIndex {
-    match val.abs() {
-        v if v < 0.5 => TileSheetIndex::MiddleGrassTile,
-        v if v > 0.5 => TileSheetIndex::MiddleSandTile,
-        _ => unreachable!("no index found"),
+    #[test]
+    fn same_seed_gives_same_collision() {
+        let first = solid_tiles(&requested_map(42));
+        let second = solid_tiles(&requested_map(42));
+        assert!(!first.is_empty());
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn collision_is_complete_before_chunks_finish_generating() {
+        let mut world = requested_map(7);
+        let tasks: Vec<(IVec2, Task<ChunkTiles>)> =
+            world.resource_mut::<GeneratingChunks>().0.drain().collect();
+        let mut generated: Vec<IVec2> = tasks
+            .into_iter()
+            .flat_map(|(chunk, task)| {
+                let origin = chunk * CHUNK_SIZE;
+                block_on(task)
+                    .decorations
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(_, decoration)| decoration.is_some_and(|kind| kind.is_solid()))
+                    .map(move |(index, _)| {
+                        let index = index as i32;
+                        origin + IVec2::new(index % CHUNK_SIZE, index / CHUNK_SIZE)
+                    })
+            })
+            .collect();
+        generated.sort_by_key(|tile| (tile.x, tile.y));
+        assert_eq!(solid_tiles(&world), generated);
     }
 }
@@ -1 +1,5 @@
+pub mod autotile;
+pub mod biome;
+pub mod collision;
+pub mod flow_field;
 pub mod map_plugin;
@@ -3,7 +3,9 @@ use std::{ops::DerefMut, time::Duration};
 use bevy::{ecs::component::Mutable, prelude::*};
 
 use crate::{
-    characters::components::{AttackCooldown, Range, SpawnCooldown, Vulnerability},
+    characters::components::{
+        AttackCooldown, HealthRegenCooldown, Range, SpawnCooldown, Vulnerability,
+    },
     GameState, MovementSpeed,
 };
 
@@ -81,6 +83,11 @@ impl Cooldown {
     pub fn is_ready(&self, period_length: Duration) -> bool {
         period_length <= self.timer
     }
+
+    /// Time left until the next period of `period_length` has elapsed.
+    pub fn remaining(&self, period_length: Duration) -> Duration {
+        period_length.saturating_sub(self.timer)
+    }
 }
 
 #[derive(Resource, Deref, DerefMut, Default)]
@@ -178,6 +185,7 @@ impl Plugin for CooldownPlugin {
                 tick_cooldown_res::<SpawnCooldown>,
                 tick_cooldown::<AttackCooldown>,
                 tick_cooldown::<Vulnerability>,
+                tick_cooldown::<HealthRegenCooldown>,
             )
                 .run_if(in_state(GameState::Running)),
         );
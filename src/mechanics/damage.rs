@@ -2,15 +2,21 @@ use std::time::Duration;
 
 use bevy::color::palettes::css;
 use bevy::{platform::collections::HashMap, prelude::*};
+use rand::Rng;
 use test_game::PROJECTILES_Z;
 
 use crate::characters::components::{
-    AttackCooldown, Enemy, Health, MaxAttackCooldown, Player, Range, Vulnerability,
+    Armor, AttackCooldown, ContactDamage, DamageRoll, Enemy, Health, MaxAttackCooldown, Player,
+    Range, Vulnerability,
 };
+use crate::mechanics::knockback::{Knockback, KnockbackResistance, KnockedBack};
+use crate::mechanics::spatial::EnemyGrid;
+use crate::mechanics::status_effects::{AppliesStatus, StatusEffects};
 use crate::prestige::stats::Stats;
 use crate::skills::skills::EnemySkills;
 use crate::sound::events::{PlaySoundEffectEvent, PlayerSound, SoundEffectKind};
 use crate::tools::damage_tracking::{DamageTracker, DamageTrackerKind};
+use crate::tools::rng::CosmeticRng;
 use crate::{
     mechanics::cooldown::Cooldown, mechanics::movement::ShouldRotate, Heading, MovementSpeed,
 };
@@ -29,9 +35,12 @@ pub struct Circle {
     pub radius: f32,
 }
 
+/// Sector whose tip is at the position of the hitbox.
 #[derive(Clone, Copy)]
 pub struct Cone {
+    /// Points from the tip to the middle of the arc, its length is the range of the cone.
     pub mid_angle: Vec2,
+    /// Half-angle of the cone, it covers `angular_width` on each side of `mid_angle`.
     pub angular_width: f32,
 }
 
@@ -57,8 +66,10 @@ impl Plugin for DamagePlugin {
                 handle_damager_with_global_hit_cooldown,
                 tick_entity_hit_cooldown,
                 handle_damage_to_player_from_enemy,
+                enemy_skill_damage.before(handle_damage_to_player_from_enemy_skills),
                 handle_damage_to_player_from_enemy_skills,
                 display_player_damage,
+                animate_damage_pop,
                 damage_multiplier,
             )
                 .run_if(in_state(GameState::Running)),
@@ -67,38 +78,100 @@ impl Plugin for DamagePlugin {
 }
 
 #[derive(Message)]
-struct PlayerDamageEvent {
-    pos: Vec2,
-    damage: Damage,
+pub struct PlayerDamageEvent {
+    pub pos: Vec2,
+    pub damage: Damage,
+    pub crit: bool,
+    /// Skill that dealt the damage, `None` for damage that is not tracked.
+    pub kind: Option<DamageTrackerKind>,
+}
+
+/// Applies the damage variance of the player and rolls for a critical hit.
+/// Returns the damage of the hit and whether it was a crit.
+pub fn roll_damage(damage: Damage, damage_roll: &DamageRoll, rng: &mut GameRng) -> (Damage, bool) {
+    if *damage == 0 {
+        return (damage, false);
+    }
+    let variance = if damage_roll.variance > 0. {
+        rng.gen_range(-damage_roll.variance..=damage_roll.variance)
+    } else {
+        0.
+    };
+    let crit = rng.gen_bool(damage_roll.crit_chance.clamp(0., 1.) as f64);
+    let multiplier = if crit {
+        damage_roll.crit_multiplier
+    } else {
+        1.
+    };
+    let rolled = (*damage as f32 * (1. + variance) * multiplier).round() as u32;
+    (Damage(rolled.max(1)), crit)
+}
+
+/// Crits start out scaled up and shrink back to their normal size over the timer.
+#[derive(Component, Deref, DerefMut)]
+struct DamagePop(Timer);
+
+const CRIT_POP_SCALE: f32 = 2.;
+
+fn damage_text_color(kind: Option<DamageTrackerKind>, crit: bool) -> Color {
+    if crit {
+        return css::GOLD.into();
+    }
+    match kind {
+        Some(DamageTrackerKind::PrimaryAttack) => css::WHITE.into(),
+        Some(DamageTrackerKind::OrbJutsu) => css::VIOLET.into(),
+        Some(DamageTrackerKind::Lightning) => css::LIGHT_SKY_BLUE.into(),
+        None => css::LIGHT_GRAY.into(),
+    }
 }
 
 fn display_player_damage(
     mut commands: Commands,
     mut dmg_event: MessageReader<PlayerDamageEvent>,
     asset_server: Res<AssetServer>,
-    mut rng: ResMut<GameRng>,
+    mut rng: ResMut<CosmeticRng>,
 ) {
-    for PlayerDamageEvent { pos, damage } in dmg_event.read() {
-        commands
-            .spawn(projectile(
-                Heading::new(Vec2::new(0., 1.)),
-                MovementSpeed(20.),
-                Range(15.),
-                ShouldRotate(false),
-            ))
-            .insert((
-                Text2d::new(format!("{:.1}", **damage)),
-                TextFont {
-                    font_size: FontSize::Px(10.0),
-                    font: asset_server.load("font/pixel-font.ttf").into(),
-                    ..default()
-                },
-                TextColor(css::WHITE.into()),
-                Transform {
-                    translation: (pos + rng.rand_vec(0., 5.)).extend(PROJECTILES_Z),
-                    ..default()
-                },
-            ));
+    for &PlayerDamageEvent {
+        pos,
+        damage,
+        crit,
+        kind,
+    } in dmg_event.read()
+    {
+        let mut text = commands.spawn(projectile(
+            Heading::new(Vec2::new(0., 1.)),
+            MovementSpeed(20.),
+            Range(15.),
+            ShouldRotate(false),
+        ));
+        text.insert((
+            Text2d::new(if crit {
+                format!("{}!", *damage)
+            } else {
+                format!("{:.1}", *damage)
+            }),
+            TextFont {
+                font_size: FontSize::Px(if crit { 16.0 } else { 10.0 }),
+                font: asset_server.load("font/pixel-font.ttf").into(),
+                ..default()
+            },
+            TextColor(damage_text_color(kind, crit)),
+            Transform {
+                translation: (pos + rng.rand_vec(0., 5.)).extend(PROJECTILES_Z),
+                ..default()
+            },
+        ));
+        if crit {
+            text.insert(DamagePop(Timer::from_seconds(0.25, TimerMode::Once)));
+        }
+    }
+}
+
+fn animate_damage_pop(time: Res<Time>, mut query: Query<(&mut DamagePop, &mut Transform)>) {
+    for (mut pop, mut transform) in &mut query {
+        pop.tick(time.delta());
+        let scale = CRIT_POP_SCALE + (1. - CRIT_POP_SCALE) * pop.fraction();
+        transform.scale = Vec3::new(scale, scale, 1.);
     }
 }
 
@@ -131,6 +204,42 @@ fn overlapping(
     }
 }
 
+/// Enemies that `hitbox` at `pos` might overlap, looked up in the [EnemyGrid].
+fn enemies_in_reach(enemy_grid: &EnemyGrid, hitbox: DealDamageHitbox, pos: Vec2) -> Vec<Entity> {
+    let reach = match hitbox {
+        DealDamageHitbox::Circle(Circle { radius }) => radius,
+        DealDamageHitbox::Cone(Cone { mid_angle, .. }) => mid_angle.length(),
+        DealDamageHitbox::Global => return enemy_grid.iter().map(|entry| entry.item).collect(),
+    };
+    enemy_grid
+        .query(pos, reach)
+        .map(|entry| entry.item)
+        .collect()
+}
+
+/// Applies the effect of a damaging entity to whatever it hit, if both sides support it.
+fn apply_status(
+    applies_status: Option<&AppliesStatus>,
+    status_effects: Option<Mut<StatusEffects>>,
+    source: Option<&DamageTrackerKind>,
+) {
+    if let (Some(applies_status), Some(mut status_effects)) = (applies_status, status_effects) {
+        status_effects.apply(**applies_status, source.copied());
+    }
+}
+
+/// Pushes whatever was hit away from the damaging entity, if both sides support it.
+fn apply_knockback(
+    knockback: Option<&Knockback>,
+    knocked_back: Option<Mut<KnockedBack>>,
+    resistance: Option<&KnockbackResistance>,
+    direction: Vec2,
+) {
+    if let (Some(knockback), Some(mut knocked_back)) = (knockback, knocked_back) {
+        knocked_back.apply(*knockback, direction, resistance);
+    }
+}
+
 /// Bundle for entity that can do contact damage
 pub fn damaging(base_damage: BaseDamage, hitbox: DealDamageHitbox) -> impl Bundle {
     (base_damage, Damage(0), hitbox)
@@ -148,15 +257,53 @@ fn handle_damager_with_hitlist(
         Option<&DamageTrackerKind>,
         &mut HitList,
         &DealDamageHitbox,
+        Option<&AppliesStatus>,
+        Option<&Knockback>,
     )>,
-    mut enemy_query: Query<(&GlobalTransform, &mut Health, &TakeDamageHitbox, Entity), With<Enemy>>,
+    mut enemy_query: Query<
+        (
+            &GlobalTransform,
+            &mut Health,
+            &TakeDamageHitbox,
+            Option<&mut StatusEffects>,
+            Option<&mut KnockedBack>,
+            Option<&KnockbackResistance>,
+        ),
+        With<Enemy>,
+    >,
+    player_query: Query<&DamageRoll, With<Player>>,
+    enemy_grid: Res<EnemyGrid>,
+    mut rng: ResMut<GameRng>,
     mut damage_events: MessageWriter<PlayerDamageEvent>,
 ) {
-    for (projectile_transform, &damage, damage_tracker_kind, mut hitlist, hitbox) in
-        damager_query.iter_mut()
+    let damage_roll = player_query.single().expect("Err");
+    for (
+        projectile_transform,
+        &damage,
+        damage_tracker_kind,
+        mut hitlist,
+        hitbox,
+        applies_status,
+        knockback,
+    ) in damager_query.iter_mut()
     {
-        for (enemy_transform, mut health, enemy_hitbox, ent) in enemy_query.iter_mut() {
-            if hitlist.contains(&ent) {
+        for entity in enemies_in_reach(
+            &enemy_grid,
+            *hitbox,
+            projectile_transform.translation().xy(),
+        ) {
+            let Ok((
+                enemy_transform,
+                mut health,
+                enemy_hitbox,
+                status_effects,
+                knocked_back,
+                resistance,
+            )) = enemy_query.get_mut(entity)
+            else {
+                continue;
+            };
+            if hitlist.contains(&entity) {
                 continue;
             }
             if overlapping(
@@ -165,6 +312,7 @@ fn handle_damager_with_hitlist(
                 *enemy_hitbox,
                 enemy_transform.translation().xy(),
             ) {
+                let (damage, crit) = roll_damage(damage, damage_roll, &mut rng);
                 if let Some(new_health) = health.checked_sub(*damage) {
                     if let Some(damage_tracker_kind) = damage_tracker_kind {
                         damage_tracker.update(*damage_tracker_kind, health.0 - new_health);
@@ -172,9 +320,19 @@ fn handle_damager_with_hitlist(
                     damage_events.write(PlayerDamageEvent {
                         pos: enemy_transform.translation().xy(),
                         damage,
+                        crit,
+                        kind: damage_tracker_kind.copied(),
                     });
                     health.0 = new_health;
-                    hitlist.push(ent);
+                    hitlist.push(entity);
+                    apply_status(applies_status, status_effects, damage_tracker_kind);
+                    apply_knockback(
+                        knockback,
+                        knocked_back,
+                        resistance,
+                        enemy_transform.translation().xy()
+                            - projectile_transform.translation().xy(),
+                    );
                 } else {
                     health.0 = 0
                 }
@@ -205,14 +363,52 @@ fn handle_damager_with_per_entity_cooldown(
         Option<&DamageTrackerKind>,
         &mut EntityHitCooldown,
         &DealDamageHitbox,
+        Option<&AppliesStatus>,
+        Option<&Knockback>,
     )>,
-    mut enemy_query: Query<(&GlobalTransform, &mut Health, &TakeDamageHitbox, Entity), With<Enemy>>,
+    mut enemy_query: Query<
+        (
+            &GlobalTransform,
+            &mut Health,
+            &TakeDamageHitbox,
+            Option<&mut StatusEffects>,
+            Option<&mut KnockedBack>,
+            Option<&KnockbackResistance>,
+        ),
+        With<Enemy>,
+    >,
+    player_query: Query<&DamageRoll, With<Player>>,
+    enemy_grid: Res<EnemyGrid>,
+    mut rng: ResMut<GameRng>,
     mut damage_events: MessageWriter<PlayerDamageEvent>,
 ) {
-    for (projectile_transform, &damage, damage_tracker_kind, mut cd, hitbox) in
-        damager_query.iter_mut()
+    let damage_roll = player_query.single().expect("Err");
+    for (
+        projectile_transform,
+        &damage,
+        damage_tracker_kind,
+        mut cd,
+        hitbox,
+        applies_status,
+        knockback,
+    ) in damager_query.iter_mut()
     {
-        for (enemy_transform, mut health, enemy_hitbox, ent) in enemy_query.iter_mut() {
+        for entity in enemies_in_reach(
+            &enemy_grid,
+            *hitbox,
+            projectile_transform.translation().xy(),
+        ) {
+            let Ok((
+                enemy_transform,
+                mut health,
+                enemy_hitbox,
+                mut status_effects,
+                mut knocked_back,
+                resistance,
+            )) = enemy_query.get_mut(entity)
+            else {
+                continue;
+            };
             if overlapping(
                 *hitbox,
                 projectile_transform.translation().xy(),
@@ -221,10 +417,11 @@ fn handle_damager_with_per_entity_cooldown(
             ) {
                 const MAXHITCOOLDOWN: f32 = 1.;
                 let hit_count = cd
-                    .entry(ent)
+                    .entry(entity)
                     .or_default()
                     .reset(Duration::from_secs_f32(MAXHITCOOLDOWN));
                 for _ in 0..hit_count {
+                    let (damage, crit) = roll_damage(damage, damage_roll, &mut rng);
                     if let Some(new_health) = health.checked_sub(*damage) {
                         if let Some(damage_tracker_kind) = damage_tracker_kind {
                             damage_tracker.update(*damage_tracker_kind, health.0 - new_health);
@@ -232,8 +429,24 @@ fn handle_damager_with_per_entity_cooldown(
                         damage_events.write(PlayerDamageEvent {
                             pos: enemy_transform.translation().xy(),
                             damage,
+                            crit,
+                            kind: damage_tracker_kind.copied(),
                         });
                         health.0 = new_health;
+                        apply_status(
+                            applies_status,
+                            status_effects.as_mut().map(|effects| effects.reborrow()),
+                            damage_tracker_kind,
+                        );
+                        apply_knockback(
+                            knockback,
+                            knocked_back
+                                .as_mut()
+                                .map(|knocked_back| knocked_back.reborrow()),
+                            resistance,
+                            enemy_transform.translation().xy()
+                                - projectile_transform.translation().xy(),
+                        );
                     } else {
                         health.0 = 0;
                         break;
@@ -255,17 +468,56 @@ fn handle_damager_with_global_hit_cooldown(
         &mut AttackCooldown,
         &MaxAttackCooldown,
         &DealDamageHitbox,
+        Option<&AppliesStatus>,
+        Option<&Knockback>,
     )>,
-    mut enemy_query: Query<(&GlobalTransform, &mut Health, &TakeDamageHitbox), With<Enemy>>,
+    mut enemy_query: Query<
+        (
+            &GlobalTransform,
+            &mut Health,
+            &TakeDamageHitbox,
+            Option<&mut StatusEffects>,
+            Option<&mut KnockedBack>,
+            Option<&KnockbackResistance>,
+        ),
+        With<Enemy>,
+    >,
+    player_query: Query<&DamageRoll, With<Player>>,
+    enemy_grid: Res<EnemyGrid>,
+    mut rng: ResMut<GameRng>,
     mut damage_events: MessageWriter<PlayerDamageEvent>,
 ) {
-    for (projectile_transform, &damage, damage_tracker_kind, mut attack_cd, max_cd, hitbox) in
-        damager_query.iter_mut()
+    let damage_roll = player_query.single().expect("Err");
+    for (
+        projectile_transform,
+        &damage,
+        damage_tracker_kind,
+        mut attack_cd,
+        max_cd,
+        hitbox,
+        applies_status,
+        knockback,
+    ) in damager_query.iter_mut()
     {
         if !attack_cd.is_ready(max_cd.0) {
             continue;
         }
-        for (enemy_transform, mut health, enemy_hitbox) in enemy_query.iter_mut() {
+        for entity in enemies_in_reach(
+            &enemy_grid,
+            *hitbox,
+            projectile_transform.translation().xy(),
+        ) {
+            let Ok((
+                enemy_transform,
+                mut health,
+                enemy_hitbox,
+                status_effects,
+                knocked_back,
+                resistance,
+            )) = enemy_query.get_mut(entity)
+            else {
+                continue;
+            };
             if overlapping(
                 *hitbox,
                 projectile_transform.translation().xy(),
@@ -276,14 +528,24 @@ fn handle_damager_with_global_hit_cooldown(
                     continue;
                 }
                 attack_cd.reset(max_cd.0);
+                let (damage, crit) = roll_damage(damage, damage_roll, &mut rng);
                 **health = health.saturating_sub(*damage);
                 damage_events.write(PlayerDamageEvent {
                     pos: enemy_transform.translation().xy(),
                     damage,
+                    crit,
+                    kind: damage_tracker_kind.copied(),
                 });
                 if let Some(damage_tracker_kind) = damage_tracker_kind {
                     damage_tracker.update(*damage_tracker_kind, *damage);
                 }
+                apply_status(applies_status, status_effects, damage_tracker_kind);
+                apply_knockback(
+                    knockback,
+                    knocked_back,
+                    resistance,
+                    enemy_transform.translation().xy() - projectile_transform.translation().xy(),
+                );
                 break;
             }
         }
@@ -292,7 +554,15 @@ fn handle_damager_with_global_hit_cooldown(
 
 /// Enemies can hit a player every tick, but only if the player has not been recently hit
 fn handle_damage_to_player_from_enemy(
-    enemy_query: Query<(&GlobalTransform, &DealDamageHitbox), With<Enemy>>,
+    enemy_query: Query<
+        (
+            &GlobalTransform,
+            &DealDamageHitbox,
+            &ContactDamage,
+            Option<&AppliesStatus>,
+        ),
+        With<Enemy>,
+    >,
     mut player_query: Query<
         (
             &GlobalTransform,
@@ -300,22 +570,32 @@ fn handle_damage_to_player_from_enemy(
             &mut Vulnerability,
             &TakeDamageHitbox,
             &mut Sprite,
+            &Armor,
+            Option<&mut StatusEffects>,
         ),
         With<Player>,
     >,
     mut sound_event: MessageWriter<PlaySoundEffectEvent>,
 ) {
-    let (player_trans, mut player_health, mut vulnerability, player_hitbox, mut sprite) =
-        player_query.single_mut().expect("Err");
+    let (
+        player_trans,
+        mut player_health,
+        mut vulnerability,
+        player_hitbox,
+        mut sprite,
+        armor,
+        status_effects,
+    ) = player_query.single_mut().expect("Err");
     let player_pos = player_trans.translation().xy();
     let invuln_timer = Duration::from_secs_f32(2.);
     if vulnerability.is_ready(invuln_timer) {
         sprite.color = sprite.color.with_alpha(1.0);
-        for (enemy_trans, enemy_hitbox) in &enemy_query {
+        for (enemy_trans, enemy_hitbox, contact_damage, applies_status) in &enemy_query {
             let enemy_pos = enemy_trans.translation().xy();
             if overlapping(*enemy_hitbox, enemy_pos, *player_hitbox, player_pos) {
-                **player_health = player_health.saturating_sub(1);
+                **player_health = player_health.saturating_sub(armor.reduce(**contact_damage));
                 vulnerability.reset(invuln_timer);
+                apply_status(applies_status, status_effects, None);
                 sound_event.write(PlaySoundEffectEvent(SoundEffectKind::Player(
                     PlayerSound::PlayerTakeDamage,
                 )));
@@ -329,7 +609,15 @@ fn handle_damage_to_player_from_enemy(
 
 /// Enemies can hit a player every tick, but only if the player has not been recently hit
 fn handle_damage_to_player_from_enemy_skills(
-    enemy_skills_query: Query<(&GlobalTransform, &DealDamageHitbox), With<EnemySkills>>,
+    enemy_skills_query: Query<
+        (
+            &GlobalTransform,
+            &DealDamageHitbox,
+            &Damage,
+            Option<&AppliesStatus>,
+        ),
+        With<EnemySkills>,
+    >,
     mut player_query: Query<
         (
             &GlobalTransform,
@@ -337,22 +625,32 @@ fn handle_damage_to_player_from_enemy_skills(
             &mut Vulnerability,
             &TakeDamageHitbox,
             &mut Sprite,
+            &Armor,
+            Option<&mut StatusEffects>,
         ),
         With<Player>,
     >,
     mut sound_event: MessageWriter<PlaySoundEffectEvent>,
 ) {
-    let (player_trans, mut player_health, mut vulnerability, player_hitbox, mut sprite) =
-        player_query.single_mut().expect("Err");
+    let (
+        player_trans,
+        mut player_health,
+        mut vulnerability,
+        player_hitbox,
+        mut sprite,
+        armor,
+        status_effects,
+    ) = player_query.single_mut().expect("Err");
     let player_pos = player_trans.translation().xy();
     let invuln_timer = Duration::from_secs_f32(2.);
     if vulnerability.is_ready(invuln_timer) {
         sprite.color = sprite.color.with_alpha(1.0);
-        for (enemy_skill_trans, enemy_hitbox) in &enemy_skills_query {
+        for (enemy_skill_trans, enemy_hitbox, damage, applies_status) in &enemy_skills_query {
             let enemy_pos = enemy_skill_trans.translation().xy();
             if overlapping(*enemy_hitbox, enemy_pos, *player_hitbox, player_pos) {
-                **player_health = player_health.saturating_sub(1);
+                **player_health = player_health.saturating_sub(armor.reduce(**damage));
                 vulnerability.reset(invuln_timer);
+                apply_status(applies_status, status_effects, None);
                 sound_event.write(PlaySoundEffectEvent(SoundEffectKind::Player(
                     PlayerSound::PlayerTakeDamage,
                 )));
@@ -364,11 +662,76 @@ fn handle_damage_to_player_from_enemy_skills(
     }
 }
 
+/// Damage of enemy skills is not affected by the prestige damage multiplier of the player.
+fn enemy_skill_damage(mut damage_query: Query<(&BaseDamage, &mut Damage), With<EnemySkills>>) {
+    for (base_damage, mut damage) in &mut damage_query {
+        **damage = **base_damage;
+    }
+}
+
 fn damage_multiplier(
-    mut damage_query: Query<(&BaseDamage, &mut Damage), Without<Enemy>>,
+    mut damage_query: Query<(&BaseDamage, &mut Damage), (Without<Enemy>, Without<EnemySkills>)>,
     stats: Res<Stats>,
 ) {
     for (base_damage, mut damage) in &mut damage_query {
         **damage = (**base_damage as f32 * stats.damage_multiplier.get_multiplier()) as u32;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use super::*;
+
+    const RANGE: f32 = 100.;
+    const TARGET_RADIUS: f32 = 0.1;
+
+    /// Whether a cone pointing along the x-axis hits a small target at `pos`.
+    fn cone_hits(angular_width: f32, pos: Vec2) -> bool {
+        overlapping(
+            DealDamageHitbox::Cone(Cone {
+                mid_angle: Vec2::new(RANGE, 0.),
+                angular_width,
+            }),
+            Vec2::ZERO,
+            TakeDamageHitbox(Circle {
+                radius: TARGET_RADIUS,
+            }),
+            pos,
+        )
+    }
+
+    fn at_angle(degrees: f32) -> Vec2 {
+        Vec2::from_angle(degrees.to_radians()) * RANGE / 2.
+    }
+
+    #[test]
+    fn cone_edges_are_half_angles() {
+        let half_angle = PI / 6.;
+        assert!(cone_hits(half_angle, at_angle(29.)));
+        assert!(cone_hits(half_angle, at_angle(-29.)));
+        assert!(!cone_hits(half_angle, at_angle(32.)));
+        assert!(!cone_hits(half_angle, at_angle(-32.)));
+    }
+
+    #[test]
+    fn cone_misses_behind_its_tip() {
+        assert!(!cone_hits(PI / 6., Vec2::new(-RANGE / 2., 0.)));
+        assert!(!cone_hits(PI / 6., at_angle(180.)));
+    }
+
+    #[test]
+    fn cone_misses_past_its_range() {
+        assert!(cone_hits(PI / 6., Vec2::new(RANGE - 1., 0.)));
+        assert!(!cone_hits(PI / 6., Vec2::new(RANGE + 1., 0.)));
+        assert!(!cone_hits(PI / 6., at_angle(20.) * 2.1));
+    }
+
+    #[test]
+    fn zero_width_cone_is_a_line() {
+        assert!(cone_hits(0., Vec2::new(RANGE / 2., 0.)));
+        assert!(cone_hits(0., Vec2::new(RANGE / 2., TARGET_RADIUS / 2.)));
+        assert!(!cone_hits(0., at_angle(1.)));
+    }
+}
@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{
+    map::collision::{CollisionGrid, MapCollider},
+    GameState, SCALE,
+};
+
+/// How fast the knockback velocity decays, per second.
+const KNOCKBACK_DECAY_RATE: f32 = 8.;
+
+/// Below this speed the knockback is stopped entirely.
+const MIN_KNOCKBACK_SPEED: f32 = 5.;
+
+/// Damaging entities with this component push the enemies they hit away from them.
+#[derive(Component, Clone, Copy)]
+pub struct Knockback {
+    /// Speed of the push right after the hit, in the same unit as [crate::MovementSpeed].
+    pub impulse: f32,
+    /// How long the enemy stops chasing the player after the hit.
+    pub hit_stun: Duration,
+}
+
+/// Fraction of knockback and hit-stun that is ignored, 1 makes the entity immune.
+#[derive(Component, Deref, Clone, Copy)]
+pub struct KnockbackResistance(pub f32);
+
+/// Knockback that is currently pushing an entity.
+#[derive(Component, Default)]
+pub struct KnockedBack {
+    velocity: Vec2,
+    hit_stun: Duration,
+}
+
+impl KnockedBack {
+    /// Pushes the entity in `direction`, the hit-stun of consecutive hits does not add up.
+    pub fn apply(
+        &mut self,
+        knockback: Knockback,
+        direction: Vec2,
+        resistance: Option<&KnockbackResistance>,
+    ) {
+        let factor = 1. - resistance.map_or(0., |resistance| resistance.clamp(0., 1.));
+        self.velocity += direction.normalize_or_zero() * knockback.impulse * factor;
+        self.hit_stun = self.hit_stun.max(knockback.hit_stun.mul_f32(factor));
+    }
+
+    pub fn is_stunned(&self) -> bool {
+        !self.hit_stun.is_zero()
+    }
+}
+
+pub struct KnockbackPlugin;
+
+impl Plugin for KnockbackPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, apply_knockback.run_if(in_state(GameState::Running)));
+    }
+}
+
+/// Moves knocked back entities and lets the push and the hit-stun wear off.
+fn apply_knockback(
+    time: Res<Time>,
+    collision_grid: Res<CollisionGrid>,
+    mut query: Query<(&mut KnockedBack, &mut Transform, Option<&MapCollider>)>,
+) {
+    let decay = (-KNOCKBACK_DECAY_RATE * time.delta_secs()).exp();
+    for (mut knocked_back, mut transform, collider) in &mut query {
+        if knocked_back.is_stunned() {
+            knocked_back.hit_stun = knocked_back.hit_stun.saturating_sub(time.delta());
+        }
+        if knocked_back.velocity == Vec2::ZERO {
+            continue;
+        }
+        let from = transform.translation.xy();
+        let to = from + knocked_back.velocity * SCALE * time.delta_secs();
+        let to = collider.map_or(to, |collider| {
+            collision_grid.slide(from, to, collider.radius)
+        });
+        (transform.translation.x, transform.translation.y) = to.into();
+        knocked_back.velocity *= decay;
+        if knocked_back.velocity.length() < MIN_KNOCKBACK_SPEED {
+            knocked_back.velocity = Vec2::ZERO;
+        }
+    }
+}
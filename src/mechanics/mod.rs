@@ -1,3 +1,6 @@
 pub mod cooldown;
 pub mod damage;
+pub mod knockback;
 pub mod movement;
+pub mod spatial;
+pub mod status_effects;
@@ -1,8 +1,9 @@
 use crate::{
     characters::components::{Player, Range},
     cleanup,
-    mechanics::cooldown::LifeTime,
-    GameState, Heading, MovementSpeed, MyGameCamera, SCALE,
+    map::collision::{CollisionGrid, CollisionResponse, MapCollider},
+    mechanics::{cooldown::LifeTime, status_effects::StatusEffects},
+    AppState, GameState, Heading, MovementSpeed, MyGameCamera, SCALE,
 };
 use bevy::prelude::*;
 use orbiting::AngularVelocity;
@@ -16,12 +17,15 @@ impl Plugin for ProjectilePlugin {
             (
                 handle_projectile_rotation,
                 player_movement,
-                sync_player_and_camera_pos,
                 speed_to_movement.before(sync_player_and_camera_pos),
                 curve_projectile,
             )
                 .run_if(in_state(GameState::Running)),
         );
+        app.add_systems(
+            Update,
+            sync_player_and_camera_pos.run_if(in_state(AppState::InGame)),
+        );
         app.add_systems(
             PostUpdate,
             sync_player_and_camera_pos.run_if(in_state(GameState::Running)),
@@ -29,6 +33,8 @@ impl Plugin for ProjectilePlugin {
     }
 }
 
+/// Also runs before the run has started, so that the map is loaded around the player
+/// and not where the camera was left by the last run.
 fn sync_player_and_camera_pos(
     player: Query<&Transform, With<Player>>,
     mut cam: Query<&mut Transform, (With<MyGameCamera>, Without<Player>)>,
@@ -41,15 +47,13 @@ fn sync_player_and_camera_pos(
 
 fn player_movement(
     keys: Res<ButtonInput<KeyCode>>,
-    mut player: Query<(&mut Transform, &mut Heading, &mut Sprite), With<Player>>,
+    mut player: Query<(&mut Heading, &mut Sprite), With<Player>>,
 ) {
-    let (mut player_trans, mut player_dir, mut player_sprite) = player.single_mut().expect("Err");
-    let player_position = &mut player_trans.translation;
+    let (mut player_dir, mut player_sprite) = player.single_mut().expect("Err");
     let keyboard_dir_x = if keys.pressed(KeyCode::KeyD) { 1. } else { 0. }
         - if keys.pressed(KeyCode::KeyA) { 1. } else { 0. };
     let keyboard_dir_y = if keys.pressed(KeyCode::KeyW) { 1. } else { 0. }
         - if keys.pressed(KeyCode::KeyS) { 1. } else { 0. };
-    const BOUND: f32 = 1900.;
 
     if keyboard_dir_x != 0. {
         if let Some(atlas) = &mut player_sprite.texture_atlas {
@@ -65,10 +69,6 @@ fn player_movement(
             atlas.index = 1;
         }
     }
-    (player_position.x, player_position.y) = player_position
-        .xy()
-        .clamp(-Vec2::splat(BOUND), Vec2::splat(BOUND))
-        .into();
     *player_dir = Heading::new(Vec2::new(keyboard_dir_x, keyboard_dir_y));
 }
 
@@ -108,11 +108,52 @@ fn handle_projectile_rotation(
     }
 }
 
-fn speed_to_movement(time: Res<Time>, mut q: Query<(&Heading, &mut Transform, &MovementSpeed)>) {
-    for (dir, mut tran, &speed) in &mut q {
+fn speed_to_movement(
+    mut commands: Commands,
+    time: Res<Time>,
+    collision_grid: Res<CollisionGrid>,
+    mut q: Query<(
+        Entity,
+        &mut Heading,
+        &mut Transform,
+        &MovementSpeed,
+        Option<&StatusEffects>,
+        Option<&MapCollider>,
+    )>,
+) {
+    for (entity, mut dir, mut tran, &speed, status_effects, collider) in &mut q {
+        let speed = *speed * status_effects.map_or(1., StatusEffects::speed_multiplier);
         let pos = &mut tran.translation;
-        (pos.x, pos.y) =
-            (Vec2::new(pos.x, pos.y) + *speed * SCALE * time.delta_secs() * dir.v).into();
+        let from = Vec2::new(pos.x, pos.y);
+        let to = from + speed * SCALE * time.delta_secs() * dir.v;
+        let to = match collider {
+            None => to,
+            Some(&MapCollider {
+                radius,
+                response: CollisionResponse::Slide,
+            }) => collision_grid.slide(from, to, radius),
+            Some(&MapCollider {
+                radius,
+                response: CollisionResponse::Bounce,
+            }) => {
+                let (to, reflection) = collision_grid.bounce(from, to, radius);
+                if reflection != Vec2::ONE {
+                    *dir = Heading::new(dir.v * reflection);
+                }
+                to
+            }
+            Some(&MapCollider {
+                radius,
+                response: CollisionResponse::Destroy,
+            }) => {
+                if collision_grid.is_blocked(to, radius) {
+                    commands.entity(entity).despawn();
+                    continue;
+                }
+                to
+            }
+        };
+        (pos.x, pos.y) = to.into();
     }
 }
 pub(super) fn curve_projectile(
@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+use test_game::{spatial_grid::SpatialGrid, ENEMY_GRID_CELL_SIZE, PICKUP_GRID_CELL_SIZE};
+
+use crate::{
+    characters::components::Enemy,
+    loot::{loot::LootId, xp::XP},
+    mechanics::damage::TakeDamageHitbox,
+    GameState,
+};
+
+/// Enemy hitboxes, rebuilt at the start of every frame.
+#[derive(Resource, Deref, DerefMut)]
+pub struct EnemyGrid(pub SpatialGrid<Entity>);
+
+impl Default for EnemyGrid {
+    fn default() -> Self {
+        Self(SpatialGrid::new(ENEMY_GRID_CELL_SIZE))
+    }
+}
+
+/// Xp orbs and loot drops, rebuilt at the start of every frame.
+#[derive(Resource, Deref, DerefMut)]
+pub struct PickupGrid(pub SpatialGrid<Entity>);
+
+impl Default for PickupGrid {
+    fn default() -> Self {
+        Self(SpatialGrid::new(PICKUP_GRID_CELL_SIZE))
+    }
+}
+
+pub struct SpatialGridPlugin;
+
+impl Plugin for SpatialGridPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PreUpdate,
+            (rebuild_enemy_grid, rebuild_pickup_grid).run_if(in_state(GameState::Running)),
+        );
+    }
+}
+
+fn rebuild_enemy_grid(
+    mut grid: ResMut<EnemyGrid>,
+    query: Query<(Entity, &GlobalTransform, &TakeDamageHitbox), With<Enemy>>,
+) {
+    grid.clear();
+    for (entity, transform, hitbox) in &query {
+        grid.insert(entity, transform.translation().xy(), hitbox.0.radius);
+    }
+}
+
+fn rebuild_pickup_grid(
+    mut grid: ResMut<PickupGrid>,
+    query: Query<(Entity, &Transform), Or<(With<XP>, With<LootId>)>>,
+) {
+    grid.clear();
+    for (entity, transform) in &query {
+        grid.insert(entity, transform.translation.xy(), 0.);
+    }
+}
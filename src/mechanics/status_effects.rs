@@ -0,0 +1,172 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{
+    characters::components::{Enemy, Health},
+    mechanics::damage::{Damage, PlayerDamageEvent},
+    tools::damage_tracking::{DamageTracker, DamageTrackerKind},
+    GameState,
+};
+
+/// Time between two ticks of damage of a [StatusEffect].
+const STATUS_EFFECT_TICK: Duration = Duration::from_millis(500);
+
+#[derive(serde::Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StatusEffectKind {
+    Burn,
+    Freeze,
+    Slow,
+    Poison,
+    Stun,
+}
+
+/// What happens when an effect is applied to an entity that already has an effect of the same kind.
+enum Stacking {
+    /// The remaining duration is extended to the new duration if that is longer
+    /// and the strongest tick damage is kept.
+    Refresh,
+    /// Adds a stack, up to `max_stacks`, and the remaining duration is extended like for [Stacking::Refresh].
+    Intensity { max_stacks: u32 },
+    /// The effect can not be applied again until it has run out.
+    IgnoreWhileActive,
+}
+
+impl StatusEffectKind {
+    fn stacking(&self) -> Stacking {
+        match self {
+            StatusEffectKind::Burn | StatusEffectKind::Freeze | StatusEffectKind::Slow => {
+                Stacking::Refresh
+            }
+            StatusEffectKind::Poison => Stacking::Intensity { max_stacks: 5 },
+            StatusEffectKind::Stun => Stacking::IgnoreWhileActive,
+        }
+    }
+
+    /// Multiplier of the movement speed while the effect is active.
+    fn speed_multiplier(&self) -> f32 {
+        match self {
+            StatusEffectKind::Freeze | StatusEffectKind::Stun => 0.,
+            StatusEffectKind::Slow => 0.5,
+            StatusEffectKind::Burn | StatusEffectKind::Poison => 1.,
+        }
+    }
+}
+
+/// An effect that lasts for `duration` and deals `tick_damage` per stack every [STATUS_EFFECT_TICK].
+#[derive(Clone, Copy)]
+pub struct StatusEffect {
+    pub kind: StatusEffectKind,
+    pub duration: Duration,
+    pub tick_damage: u32,
+}
+
+/// Damaging entities with this component apply the effect to everything they hit.
+#[derive(Component, Deref, Clone, Copy)]
+pub struct AppliesStatus(pub StatusEffect);
+
+struct ActiveStatusEffect {
+    effect: StatusEffect,
+    stacks: u32,
+    remaining: Duration,
+    tick: Timer,
+    /// Tick damage is credited to the skill that applied the effect.
+    source: Option<DamageTrackerKind>,
+}
+
+/// Every effect currently active on an entity.
+#[derive(Component, Default)]
+pub struct StatusEffects(Vec<ActiveStatusEffect>);
+
+impl StatusEffects {
+    pub fn apply(&mut self, effect: StatusEffect, source: Option<DamageTrackerKind>) {
+        let Some(active) = self.0.iter_mut().find(|a| a.effect.kind == effect.kind) else {
+            self.0.push(ActiveStatusEffect {
+                effect,
+                stacks: 1,
+                remaining: effect.duration,
+                tick: Timer::new(STATUS_EFFECT_TICK, TimerMode::Repeating),
+                source,
+            });
+            return;
+        };
+        match effect.kind.stacking() {
+            Stacking::Refresh => {
+                active.remaining = active.remaining.max(effect.duration);
+                if effect.tick_damage > active.effect.tick_damage {
+                    active.effect.tick_damage = effect.tick_damage;
+                    active.source = source;
+                }
+            }
+            Stacking::Intensity { max_stacks } => {
+                active.stacks = (active.stacks + 1).min(max_stacks);
+                active.remaining = active.remaining.max(effect.duration);
+            }
+            Stacking::IgnoreWhileActive => {}
+        }
+    }
+
+    pub fn has(&self, kind: StatusEffectKind) -> bool {
+        self.0.iter().any(|active| active.effect.kind == kind)
+    }
+
+    /// The strongest movement speed modifier of all active effects.
+    pub fn speed_multiplier(&self) -> f32 {
+        self.0
+            .iter()
+            .map(|active| active.effect.kind.speed_multiplier())
+            .fold(1., f32::min)
+    }
+}
+
+pub struct StatusEffectsPlugin;
+
+impl Plugin for StatusEffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            tick_status_effects.run_if(in_state(GameState::Running)),
+        );
+    }
+}
+
+/// Deals the tick damage of every active effect and removes the effects that have run out.
+fn tick_status_effects(
+    time: Res<Time>,
+    mut damage_tracker: ResMut<DamageTracker>,
+    mut query: Query<(
+        &mut StatusEffects,
+        &mut Health,
+        &GlobalTransform,
+        Has<Enemy>,
+    )>,
+    mut damage_events: MessageWriter<PlayerDamageEvent>,
+) {
+    for (mut status_effects, mut health, transform, is_enemy) in &mut query {
+        for active in status_effects.0.iter_mut() {
+            active.tick.tick(time.delta());
+            active.remaining = active.remaining.saturating_sub(time.delta());
+            let ticks = active.tick.times_finished_this_tick();
+            let damage = active.effect.tick_damage * active.stacks * ticks;
+            if damage == 0 || **health == 0 {
+                continue;
+            }
+            let dealt = (**health).min(damage);
+            **health -= dealt;
+            if is_enemy {
+                if let Some(source) = active.source {
+                    damage_tracker.update(source, dealt);
+                }
+                damage_events.write(PlayerDamageEvent {
+                    pos: transform.translation().xy(),
+                    damage: Damage(dealt),
+                    crit: false,
+                    kind: active.source,
+                });
+            }
+        }
+        status_effects
+            .0
+            .retain(|active| !active.remaining.is_zero());
+    }
+}
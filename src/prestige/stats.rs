@@ -8,6 +8,8 @@ pub enum UpgradeOptions {
     MaximumHealth,
     HealthRegen,
     DamageMultiplier,
+    RerollBanish,
+    CritChance,
 }
 
 pub trait PrestigeTier: Sized {
@@ -70,8 +72,8 @@ impl PrestigeTier for DamageMultiplierTier {
 pub struct MaximumHealthTier(u32);
 
 impl MaximumHealthTier {
-    fn get_increase(&self) -> u32 {
-        self.0
+    pub fn get_increase(&self) -> u32 {
+        self.0 * 10
     }
 }
 
@@ -115,7 +117,7 @@ impl PrestigeTier for MaximumHealthTier {
 pub struct HealthRegenTier(u32);
 
 impl HealthRegenTier {
-    fn get_increase(&self) -> Option<Duration> {
+    pub fn get_increase(&self) -> Option<Duration> {
         if self.0 > 0 {
             return Some(Duration::from_secs(60 - self.0 as u64 * 5));
         }
@@ -164,12 +166,117 @@ impl PrestigeTier for HealthRegenTier {
     }
 }
 
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Default)]
+pub struct RerollBanishTier(u32);
+
+impl RerollBanishTier {
+    pub fn get_rerolls(&self) -> u32 {
+        self.0
+    }
+
+    pub fn get_banishes(&self) -> u32 {
+        self.0.div_ceil(2)
+    }
+}
+
+impl PrestigeTier for RerollBanishTier {
+    const MAX_TIER: u32 = 5;
+
+    fn price(&self) -> u32 {
+        10 * self.0
+    }
+
+    fn description(&self) -> String {
+        match self.next() {
+            Some(next) => {
+                format!(
+                    "Rerolls per run: {} (+{}), Banishes per run: {} (+{}),  Tier {}/{}",
+                    self.get_rerolls(),
+                    next.get_rerolls() - self.get_rerolls(),
+                    self.get_banishes(),
+                    next.get_banishes() - self.get_banishes(),
+                    self.0,
+                    Self::MAX_TIER
+                )
+            }
+            None => {
+                format!(
+                    "Rerolls per run: {}, Banishes per run: {},  Tier {}/{}",
+                    self.get_rerolls(),
+                    self.get_banishes(),
+                    self.0,
+                    self.0
+                )
+            }
+        }
+    }
+
+    fn next(&self) -> Option<Self> {
+        if self.0 == Self::MAX_TIER {
+            return None;
+        }
+        Some(Self(self.0 + 1))
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Default)]
+pub struct CritChanceTier(u32);
+
+impl CritChanceTier {
+    pub fn get_increase(&self) -> f32 {
+        self.0 as f32 * 0.02
+    }
+}
+
+impl PrestigeTier for CritChanceTier {
+    const MAX_TIER: u32 = 5;
+
+    fn price(&self) -> u32 {
+        10 * self.0
+    }
+
+    fn description(&self) -> String {
+        match self.next() {
+            Some(next) => {
+                format!(
+                    "Increase critical hit chance by: {:.0}% (+{:.0}%),  Tier {}/{}",
+                    self.get_increase() * 100.,
+                    (next.get_increase() - self.get_increase()) * 100.,
+                    self.0,
+                    Self::MAX_TIER
+                )
+            }
+            None => {
+                format!(
+                    "Increase critical hit chance by: {:.0}%,  Tier {}/{}",
+                    self.get_increase() * 100.,
+                    self.0,
+                    self.0
+                )
+            }
+        }
+    }
+
+    fn next(&self) -> Option<Self> {
+        if self.0 == Self::MAX_TIER {
+            return None;
+        }
+        Some(Self(self.0 + 1))
+    }
+}
+
 #[derive(Resource, serde::Serialize, serde::Deserialize, Clone, Copy)]
 pub struct Stats {
     pub coins: u32,
     pub damage_multiplier: DamageMultiplierTier,
     pub maximum_health: MaximumHealthTier,
     pub health_regen: HealthRegenTier,
+    /// Missing in saves from before rerolls and banishes existed.
+    #[serde(default)]
+    pub reroll_banish: RerollBanishTier,
+    /// Missing in saves from before critical hits existed.
+    #[serde(default)]
+    pub crit_chance: CritChanceTier,
 }
 
 impl Default for Stats {
@@ -179,6 +286,8 @@ impl Default for Stats {
             damage_multiplier: DamageMultiplierTier(0),
             maximum_health: MaximumHealthTier(0),
             health_regen: HealthRegenTier(0),
+            reroll_banish: RerollBanishTier(0),
+            crit_chance: CritChanceTier(0),
         }
     }
 }
@@ -233,6 +342,26 @@ impl Stats {
         }
     }
 
+    fn uprade_reroll_banish(&mut self) {
+        let Some(next) = self.reroll_banish.next() else {
+            return;
+        };
+        if self.coins >= next.price() {
+            self.coins -= next.price();
+            self.reroll_banish = next;
+        }
+    }
+
+    fn uprade_crit_chance(&mut self) {
+        let Some(next) = self.crit_chance.next() else {
+            return;
+        };
+        if self.coins >= next.price() {
+            self.coins -= next.price();
+            self.crit_chance = next;
+        }
+    }
+
     pub fn upgrade(&mut self, upgrade_option: UpgradeOptions) {
         match upgrade_option {
             UpgradeOptions::MaximumHealth => {
@@ -244,6 +373,12 @@ impl Stats {
             UpgradeOptions::DamageMultiplier => {
                 self.uprade_damage_multiplier();
             }
+            UpgradeOptions::RerollBanish => {
+                self.uprade_reroll_banish();
+            }
+            UpgradeOptions::CritChance => {
+                self.uprade_crit_chance();
+            }
         }
     }
 
@@ -252,6 +387,8 @@ impl Stats {
             UpgradeOptions::MaximumHealth => self.maximum_health.description(),
             UpgradeOptions::HealthRegen => self.health_regen.description(),
             UpgradeOptions::DamageMultiplier => self.damage_multiplier.description(),
+            UpgradeOptions::RerollBanish => self.reroll_banish.description(),
+            UpgradeOptions::CritChance => self.crit_chance.description(),
         }
     }
 
@@ -260,6 +397,8 @@ impl Stats {
             UpgradeOptions::MaximumHealth => Some(self.maximum_health.next()?.price()),
             UpgradeOptions::HealthRegen => Some(self.health_regen.next()?.price()),
             UpgradeOptions::DamageMultiplier => Some(self.damage_multiplier.next()?.price()),
+            UpgradeOptions::RerollBanish => Some(self.reroll_banish.next()?.price()),
+            UpgradeOptions::CritChance => Some(self.crit_chance.next()?.price()),
         }
     }
 
@@ -267,3 +406,67 @@ impl Stats {
         Some(self.get_next_price(upgrade_option)? <= self.coins)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every tier from 0 up to `MAX_TIER`, following `next`.
+    fn tiers<T: PrestigeTier + Copy>(first: T) -> Vec<T> {
+        std::iter::successors(Some(first), |tier| tier.next()).collect()
+    }
+
+    #[test]
+    fn maximum_health_tiers() {
+        let tiers = tiers(MaximumHealthTier(0));
+        assert_eq!(
+            tiers
+                .iter()
+                .map(|tier| tier.get_increase())
+                .collect::<Vec<_>>(),
+            [0, 10, 20, 30, 40, 50]
+        );
+        assert_eq!(
+            tiers.iter().map(|tier| tier.price()).collect::<Vec<_>>(),
+            [0, 10, 20, 30, 40, 50]
+        );
+        assert!(MaximumHealthTier(MaximumHealthTier::MAX_TIER)
+            .next()
+            .is_none());
+    }
+
+    #[test]
+    fn health_regen_tiers() {
+        let tiers = tiers(HealthRegenTier(0));
+        assert_eq!(
+            tiers
+                .iter()
+                .map(|tier| tier.get_increase().map(|period| period.as_secs()))
+                .collect::<Vec<_>>(),
+            [None, Some(55), Some(50), Some(45), Some(40), Some(35)]
+        );
+        assert_eq!(
+            tiers.iter().map(|tier| tier.price()).collect::<Vec<_>>(),
+            [0, 10, 20, 30, 40, 50]
+        );
+        assert!(HealthRegenTier(HealthRegenTier::MAX_TIER).next().is_none());
+    }
+
+    #[test]
+    fn upgrade_pays_for_the_next_tier() {
+        let mut stats = Stats {
+            coins: 25,
+            ..Stats::default()
+        };
+        stats.upgrade(UpgradeOptions::MaximumHealth);
+        stats.upgrade(UpgradeOptions::MaximumHealth);
+        assert_eq!(stats.maximum_health.get_increase(), 10);
+        assert_eq!(stats.coins, 15);
+        stats.upgrade(UpgradeOptions::HealthRegen);
+        assert_eq!(
+            stats.health_regen.get_increase(),
+            Some(Duration::from_secs(55))
+        );
+        assert_eq!(stats.coins, 5);
+    }
+}
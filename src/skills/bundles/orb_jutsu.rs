@@ -1,8 +1,11 @@
+use std::time::Duration;
+
 use bevy::ecs::bundle::Bundle;
 
 use crate::{
     mechanics::{
         damage::{damaging, BaseDamage, Circle, DealDamageHitbox, EntityHitCooldown},
+        knockback::Knockback,
         movement::orbiting::{Angle, AngularVelocity, OrbitalRadius},
     },
     sprites::{Skill, SpriteKind},
@@ -10,16 +13,20 @@ use crate::{
     SCALE,
 };
 
-pub fn orb_jutsu_bundle(angle: f32) -> impl Bundle {
+pub fn orb_jutsu_bundle(angle: f32, damage: u32) -> impl Bundle {
     (
         AngularVelocity(3.),
         Angle(angle),
         OrbitalRadius(200. * SCALE),
         damaging(
-            BaseDamage(10),
+            BaseDamage(damage),
             DealDamageHitbox::Circle(Circle { radius: 20. }),
         ),
         EntityHitCooldown::default(),
+        Knockback {
+            impulse: 350.,
+            hit_stun: Duration::from_millis(200),
+        },
         SpriteKind::Skill(Skill::OrbJutsu),
         DamageTrackerKind::OrbJutsu,
     )
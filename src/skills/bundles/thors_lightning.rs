@@ -3,30 +3,47 @@ use std::time::Duration;
 use bevy::{
     ecs::{bundle::Bundle, component::Component},
     math::Vec3,
+    prelude::{Deref, DerefMut},
     transform::components::Transform,
     utils::default,
 };
 use test_game::PROJECTILES_Z;
 
 use crate::{
-    characters::components::{AttackCooldown, MaxAttackCooldown},
+    characters::components::{AttackCooldown, MaxAttackCooldown, Range},
+    cleanup,
     mechanics::{
         cooldown::LifeTime,
-        damage::{damaging, BaseDamage, Circle, DealDamageHitbox},
+        damage::{BaseDamage, Damage},
+        status_effects::{AppliesStatus, StatusEffect, StatusEffectKind},
     },
+    skills::skill_levels::ThorsLightningLevel,
     sprites::{Skill, SpriteKind},
     tools::damage_tracking::DamageTrackerKind,
 };
 
-pub fn thors_lightning_bundle() -> impl Bundle {
+#[derive(Component)]
+pub struct ThorsLightningMarker;
+
+/// Number of enemies struck every time the lightning cooldown is ready.
+#[derive(Component, Deref, DerefMut, Clone, Copy)]
+pub struct LightningStrikeCount(pub u32);
+
+pub fn thors_lightning_bundle(level: ThorsLightningLevel) -> impl Bundle {
     (
+        ThorsLightningMarker,
         AttackCooldown(default()),
-        MaxAttackCooldown(Duration::from_secs_f32(5.0)),
-        damaging(
-            BaseDamage(10),
-            DealDamageHitbox::Circle(Circle { radius: 100.0 }),
-        ),
+        MaxAttackCooldown(Duration::from_secs_f32(level.cooldown_secs)),
+        BaseDamage(level.damage),
+        Damage(0),
+        Range(100.0),
+        LightningStrikeCount(level.strikes),
         DamageTrackerKind::Lightning,
+        AppliesStatus(StatusEffect {
+            kind: StatusEffectKind::Stun,
+            duration: Duration::from_millis(500),
+            tick_damage: 0,
+        }),
         Transform::default(),
     )
 }
@@ -34,9 +51,10 @@ pub fn thors_lightning_bundle() -> impl Bundle {
 #[derive(Component)]
 pub struct LightningEffectMarker;
 
-/// TODO: Should be spawned on lightning hit...
+/// Visual effect of a single lightning strike, spawned on top of the struck enemy.
 pub fn thors_lightning_strike_bundle(x: f32, y: f32) -> impl Bundle {
     (
+        cleanup::ExitGame,
         Transform::from_translation(Vec3::new(x, y, PROJECTILES_Z)),
         LightningEffectMarker,
         LifeTime::from_secs_f32(0.3),
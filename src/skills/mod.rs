@@ -1,8 +1,10 @@
 use bevy::prelude::*;
-use skills::animate_lightning;
+use skills::{animate_lightning, spawn_lightning};
 
 use crate::GameState;
-mod bundles;
+pub mod bundles;
+pub mod skill_levels;
+pub mod skill_offers;
 pub mod skills;
 pub mod skills_tooltips;
 
@@ -12,7 +14,7 @@ impl Plugin for SkillsPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            (animate_lightning,).run_if(in_state(GameState::Running)),
+            (animate_lightning, spawn_lightning).run_if(in_state(GameState::Running)),
         );
     }
 }
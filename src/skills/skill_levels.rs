@@ -0,0 +1,136 @@
+use bevy::{
+    ecs::{resource::Resource, system::ResMut},
+    platform::collections::HashMap,
+};
+
+use super::skills_tooltips::SkillType;
+
+/// Stats of Orb Jutsu at a given level.
+#[derive(Clone, Copy)]
+pub struct OrbJutsuLevel {
+    pub orb_count: u32,
+    pub damage: u32,
+}
+
+/// Per-level table for Orb Jutsu, index 0 is level 1.
+pub const ORB_JUTSU_LEVELS: [OrbJutsuLevel; 5] = [
+    OrbJutsuLevel {
+        orb_count: 1,
+        damage: 10,
+    },
+    OrbJutsuLevel {
+        orb_count: 2,
+        damage: 10,
+    },
+    OrbJutsuLevel {
+        orb_count: 3,
+        damage: 12,
+    },
+    OrbJutsuLevel {
+        orb_count: 4,
+        damage: 14,
+    },
+    OrbJutsuLevel {
+        orb_count: 5,
+        damage: 18,
+    },
+];
+
+/// Stats of Thor's Lightning at a given level.
+#[derive(Clone, Copy)]
+pub struct ThorsLightningLevel {
+    pub strikes: u32,
+    pub damage: u32,
+    pub cooldown_secs: f32,
+}
+
+/// Per-level table for Thor's Lightning, index 0 is level 1.
+pub const THORS_LIGHTNING_LEVELS: [ThorsLightningLevel; 5] = [
+    ThorsLightningLevel {
+        strikes: 1,
+        damage: 10,
+        cooldown_secs: 5.0,
+    },
+    ThorsLightningLevel {
+        strikes: 2,
+        damage: 10,
+        cooldown_secs: 4.5,
+    },
+    ThorsLightningLevel {
+        strikes: 2,
+        damage: 15,
+        cooldown_secs: 4.0,
+    },
+    ThorsLightningLevel {
+        strikes: 3,
+        damage: 15,
+        cooldown_secs: 3.5,
+    },
+    ThorsLightningLevel {
+        strikes: 4,
+        damage: 20,
+        cooldown_secs: 3.0,
+    },
+];
+
+/// Max level for the passive skills, which apply the same increase on every level.
+const PASSIVE_MAX_LEVEL: u32 = 5;
+
+impl SkillType {
+    pub fn max_level(&self) -> u32 {
+        match self {
+            SkillType::PassiveDamageIncrease
+            | SkillType::PassiveMovementSpeedIncrease
+            | SkillType::PassivePickUpRadiusIncrease
+            | SkillType::PassiveHealthIncrease
+            | SkillType::PassiveCritChance
+            | SkillType::PassiveArmor => PASSIVE_MAX_LEVEL,
+            SkillType::ActiveOrbitingOrb => ORB_JUTSU_LEVELS.len() as u32,
+            SkillType::ActiveThorLightning => THORS_LIGHTNING_LEVELS.len() as u32,
+        }
+    }
+}
+
+/// Returns the Orb Jutsu stats for `level`, or `None` if the skill is not learned.
+pub fn orb_jutsu_level(level: u32) -> Option<OrbJutsuLevel> {
+    ORB_JUTSU_LEVELS
+        .get(level.checked_sub(1)? as usize)
+        .copied()
+}
+
+/// Returns the Thor's Lightning stats for `level`, or `None` if the skill is not learned.
+pub fn thors_lightning_level(level: u32) -> Option<ThorsLightningLevel> {
+    THORS_LIGHTNING_LEVELS
+        .get(level.checked_sub(1)? as usize)
+        .copied()
+}
+
+/// Level of every [SkillType] picked during the current run.
+/// Skills that have never been picked are level 0.
+#[derive(Resource, Default)]
+pub struct SkillLevels(HashMap<SkillType, u32>);
+
+impl SkillLevels {
+    pub fn level(&self, skill: SkillType) -> u32 {
+        self.0.get(&skill).copied().unwrap_or(0)
+    }
+
+    pub fn is_max_level(&self, skill: SkillType) -> bool {
+        skill.max_level() <= self.level(skill)
+    }
+
+    /// Increases the level of `skill` by one, up to its max level, and returns the new level.
+    pub fn level_up(&mut self, skill: SkillType) -> u32 {
+        let level = self.0.entry(skill).or_insert(0);
+        *level = (*level + 1).min(skill.max_level());
+        *level
+    }
+
+    pub fn reset(&mut self) {
+        self.0.clear();
+    }
+}
+
+pub fn reset_skill_levels(mut skill_levels: ResMut<SkillLevels>) {
+    skill_levels.reset();
+}
@@ -0,0 +1,231 @@
+use bevy::{
+    color::{palettes::css, Color},
+    ecs::{
+        resource::Resource,
+        system::{Res, ResMut},
+    },
+    platform::collections::HashSet,
+    prelude::{Deref, DerefMut},
+};
+use rand::distributions::{Distribution, WeightedIndex};
+
+use crate::{prestige::stats::Stats, tools::rng::GameRng};
+
+use super::{skill_levels::SkillLevels, skills_tooltips::SkillType};
+
+/// Amount of skills offered on every level up.
+pub const OFFER_COUNT: usize = 3;
+
+/// Multiplier applied to the weight of skills the player already owns.
+const OWNED_WEIGHT_MULTIPLIER: u32 = 2;
+
+/// Rarity tier of a [SkillType], decides how often it is offered and the color of its card.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Rarity {
+    Common,
+    Rare,
+    Epic,
+}
+
+impl Rarity {
+    /// Base weight of the rarity when generating offers.
+    pub fn weight(&self) -> u32 {
+        match self {
+            Rarity::Common => 10,
+            Rarity::Rare => 5,
+            Rarity::Epic => 2,
+        }
+    }
+
+    pub fn card_color(&self) -> Color {
+        match self {
+            Rarity::Common => css::DARK_GRAY.into(),
+            Rarity::Rare => css::MIDNIGHT_BLUE.into(),
+            Rarity::Epic => css::INDIGO.into(),
+        }
+    }
+
+    pub fn hover_color(&self) -> Color {
+        match self {
+            Rarity::Common => css::GRAY.into(),
+            Rarity::Rare => css::ROYAL_BLUE.into(),
+            Rarity::Epic => css::PURPLE.into(),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rarity::Common => "Common",
+            Rarity::Rare => "Rare",
+            Rarity::Epic => "Epic",
+        }
+    }
+}
+
+impl SkillType {
+    pub fn rarity(&self) -> Rarity {
+        match self {
+            SkillType::PassiveMovementSpeedIncrease
+            | SkillType::PassivePickUpRadiusIncrease
+            | SkillType::PassiveHealthIncrease
+            | SkillType::PassiveArmor => Rarity::Common,
+            SkillType::PassiveDamageIncrease
+            | SkillType::PassiveCritChance
+            | SkillType::ActiveOrbitingOrb => Rarity::Rare,
+            SkillType::ActiveThorLightning => Rarity::Epic,
+        }
+    }
+
+    /// Weight of the skill when generating offers, skills the player already owns are offered more often.
+    pub fn offer_weight(&self, skill_levels: &SkillLevels) -> u32 {
+        if skill_levels.level(*self) > 0 {
+            self.rarity().weight() * OWNED_WEIGHT_MULTIPLIER
+        } else {
+            self.rarity().weight()
+        }
+    }
+}
+
+/// Picks up to `count` distinct skills out of `pool` by their offer weight.
+/// Skills at max level or in `banished` are never offered.
+/// The result only depends on the state of `rng`, so the same seed gives the same offers.
+pub fn generate_offers(
+    rng: &mut GameRng,
+    pool: &[SkillType],
+    skill_levels: &SkillLevels,
+    banished: &HashSet<SkillType>,
+    count: usize,
+) -> Vec<SkillType> {
+    let mut candidates: Vec<(SkillType, u32)> = pool
+        .iter()
+        .filter(|skill| !skill_levels.is_max_level(**skill) && !banished.contains(*skill))
+        .map(|skill| (*skill, skill.offer_weight(skill_levels)))
+        .collect();
+    let mut offers = Vec::with_capacity(count);
+    while offers.len() < count {
+        let Ok(weights) = WeightedIndex::new(candidates.iter().map(|(_, weight)| *weight)) else {
+            break;
+        };
+        let (skill, _) = candidates.remove(weights.sample(&mut **rng));
+        offers.push(skill);
+    }
+    offers
+}
+
+/// The skills currently offered on the level up screen.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct LevelUpOffers(pub Vec<SkillType>);
+
+/// Rerolls and banishes left for the current run, and the skills banished so far.
+#[derive(Resource, Default)]
+pub struct RerollBanishCharges {
+    pub rerolls: u32,
+    pub banishes: u32,
+    pub banished: HashSet<SkillType>,
+    /// Whether the next picked card gets banished instead of learned.
+    pub banishing: bool,
+}
+
+pub fn reset_reroll_banish_charges(mut charges: ResMut<RerollBanishCharges>, stats: Res<Stats>) {
+    *charges = RerollBanishCharges {
+        rerolls: stats.reroll_banish.get_rerolls(),
+        banishes: stats.reroll_banish.get_banishes(),
+        ..Default::default()
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POOL: [SkillType; 8] = [
+        SkillType::PassiveDamageIncrease,
+        SkillType::PassiveMovementSpeedIncrease,
+        SkillType::PassivePickUpRadiusIncrease,
+        SkillType::PassiveHealthIncrease,
+        SkillType::PassiveCritChance,
+        SkillType::PassiveArmor,
+        SkillType::ActiveOrbitingOrb,
+        SkillType::ActiveThorLightning,
+    ];
+
+    fn max_out(skill_levels: &mut SkillLevels, skill: SkillType) {
+        for _ in 0..skill.max_level() {
+            skill_levels.level_up(skill);
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_same_offers() {
+        let skill_levels = SkillLevels::default();
+        let banished = HashSet::new();
+        let mut first = GameRng::new(42);
+        let mut second = GameRng::new(42);
+        for _ in 0..20 {
+            let offers = generate_offers(&mut first, &POOL, &skill_levels, &banished, OFFER_COUNT);
+            assert!(
+                offers
+                    == generate_offers(&mut second, &POOL, &skill_levels, &banished, OFFER_COUNT)
+            );
+            assert_eq!(offers.len(), OFFER_COUNT);
+        }
+    }
+
+    #[test]
+    fn max_level_and_banished_skills_are_never_offered() {
+        let mut skill_levels = SkillLevels::default();
+        max_out(&mut skill_levels, SkillType::ActiveOrbitingOrb);
+        max_out(&mut skill_levels, SkillType::PassiveArmor);
+        let banished = HashSet::from_iter([SkillType::PassiveDamageIncrease]);
+        let mut rng = GameRng::new(7);
+        for _ in 0..200 {
+            let offers = generate_offers(&mut rng, &POOL, &skill_levels, &banished, OFFER_COUNT);
+            assert_eq!(offers.len(), OFFER_COUNT);
+            assert!(!offers.contains(&SkillType::ActiveOrbitingOrb));
+            assert!(!offers.contains(&SkillType::PassiveArmor));
+            assert!(!offers.contains(&SkillType::PassiveDamageIncrease));
+        }
+    }
+
+    #[test]
+    fn owned_skills_are_offered_more_often() {
+        let mut skill_levels = SkillLevels::default();
+        skill_levels.level_up(SkillType::PassiveArmor);
+        assert_eq!(
+            SkillType::PassiveArmor.offer_weight(&skill_levels),
+            Rarity::Common.weight() * OWNED_WEIGHT_MULTIPLIER
+        );
+        assert_eq!(
+            SkillType::PassiveHealthIncrease.offer_weight(&skill_levels),
+            Rarity::Common.weight()
+        );
+
+        let pool = [SkillType::PassiveArmor, SkillType::PassiveHealthIncrease];
+        let banished = HashSet::new();
+        let mut rng = GameRng::new(3);
+        let draws = 3000;
+        let owned_picks = (0..draws)
+            .filter(|_| {
+                generate_offers(&mut rng, &pool, &skill_levels, &banished, 1)
+                    == [SkillType::PassiveArmor]
+            })
+            .count();
+        let owned_share = owned_picks as f32 / draws as f32;
+        assert!((0.6..0.73).contains(&owned_share), "{owned_share}");
+    }
+
+    #[test]
+    fn fewer_eligible_skills_than_offers() {
+        let mut skill_levels = SkillLevels::default();
+        for skill in &POOL[1..] {
+            max_out(&mut skill_levels, *skill);
+        }
+        let mut rng = GameRng::new(1);
+        let offers = generate_offers(&mut rng, &POOL, &skill_levels, &HashSet::new(), OFFER_COUNT);
+        assert!(offers == [SkillType::PassiveDamageIncrease]);
+
+        max_out(&mut skill_levels, POOL[0]);
+        let offers = generate_offers(&mut rng, &POOL, &skill_levels, &HashSet::new(), OFFER_COUNT);
+        assert!(offers.is_empty());
+    }
+}
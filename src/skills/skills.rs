@@ -1,101 +1,183 @@
-use bevy::prelude::*;
-use std::f32::consts::TAU;
+use bevy::{ecs::system::SystemParam, prelude::*};
+use rand::prelude::*;
+use std::{f32::consts::TAU, time::Duration};
 
 use crate::{
-    mechanics::{cooldown::LifeTime, movement::orbiting::OrbitalRadius},
+    characters::components::{
+        AttackCooldown, DamageRoll, Enemy, Health, MaxAttackCooldown, Player, Range,
+    },
+    mechanics::{
+        cooldown::LifeTime,
+        damage::{roll_damage, BaseDamage, Damage, PlayerDamageEvent},
+        movement::orbiting::OrbitalRadius,
+        spatial::EnemyGrid,
+        status_effects::{AppliesStatus, StatusEffects},
+    },
     skills::bundles::{
         orb_jutsu::orb_jutsu_bundle,
-        thors_lightning::{thors_lightning_bundle, LightningEffectMarker},
+        thors_lightning::{
+            thors_lightning_bundle, thors_lightning_strike_bundle, LightningEffectMarker,
+            LightningStrikeCount, ThorsLightningMarker,
+        },
     },
+    skills::skill_levels::{OrbJutsuLevel, ThorsLightningLevel},
+    sound::events::{PlaySoundEffectEvent, SkillSound, SoundEffectKind},
+    tools::damage_tracking::{DamageTracker, DamageTrackerKind},
+    GameRng,
 };
 
 #[derive(Component)]
 pub struct EnemySkills;
 
-/// This func handles correct angle distance between orb projectiles.
-pub fn spawn_new_orb(
+/// Replaces the player's orbs with the amount given by `orb_jutsu`, evenly spread around the player.
+pub fn spawn_orbs(
     commands: &mut Commands,
     player_entity: Entity,
     query_orb: &mut Query<Entity, With<OrbitalRadius>>,
+    orb_jutsu: OrbJutsuLevel,
 ) {
-    // will at least spawn 1 orb.
-    let mut orb_counter = 1;
     for entity in query_orb {
-        orb_counter += 1;
         commands.entity(entity).despawn();
     }
     commands.entity(player_entity).with_children(|parent| {
-        for i in 0..orb_counter {
-            let angle = if i == 0 {
-                0.0
-            } else {
-                (TAU / orb_counter as f32) * i as f32
-            };
-            parent.spawn(orb_jutsu_bundle(angle));
+        for i in 0..orb_jutsu.orb_count {
+            let angle = (TAU / orb_jutsu.orb_count as f32) * i as f32;
+            parent.spawn(orb_jutsu_bundle(angle, orb_jutsu.damage));
         }
     });
 }
 
-pub fn enable_thors_lightning_skill(commands: &mut Commands, player_entity: Entity) {
+/// Spawns Thor's Lightning the first time it is picked, later picks update it to the stats of the new level.
+pub fn enable_thors_lightning_skill(
+    commands: &mut Commands,
+    player_entity: Entity,
+    lightning_query: &mut Query<
+        (
+            &mut LightningStrikeCount,
+            &mut BaseDamage,
+            &mut MaxAttackCooldown,
+        ),
+        With<ThorsLightningMarker>,
+    >,
+    thors_lightning: ThorsLightningLevel,
+) {
+    if let Some((mut strike_count, mut base_damage, mut max_attack_cooldown)) =
+        lightning_query.iter_mut().next()
+    {
+        **strike_count = thors_lightning.strikes;
+        **base_damage = thors_lightning.damage;
+        **max_attack_cooldown = Duration::from_secs_f32(thors_lightning.cooldown_secs);
+        return;
+    }
     commands.entity(player_entity).with_children(|child| {
-        child.spawn(thors_lightning_bundle());
+        child.spawn(thors_lightning_bundle(thors_lightning));
     });
 }
-/*
+
+/// The player's crit chance and damage variance, the rng they are rolled with
+/// and the damage numbers the rolls are shown as.
+#[derive(SystemParam)]
+pub struct PlayerDamageRolls<'w, 's> {
+    rng: ResMut<'w, GameRng>,
+    player_query: Query<'w, 's, &'static DamageRoll, With<Player>>,
+    damage_events: MessageWriter<'w, PlayerDamageEvent>,
+}
+
+/// Strikes random enemies within range every time the lightning cooldown is ready.
+/// The cooldown waits while no enemy is in range, so the next enemy to come close is struck right away.
 pub fn spawn_lightning(
     mut commands: Commands,
     mut damage_tracker: ResMut<DamageTracker>,
-    player_query: Query<&Transform, With<Player>>,
-    mut enemy_query: Query<(&mut Health, &Transform), With<Enemy>>,
-    asset_server: Res<AssetServer>,
+    rolls: PlayerDamageRolls,
     mut lightning_query: Query<
         (
             &mut AttackCooldown,
             &MaxAttackCooldown,
-            &DealDamageHitBox,
+            &Range,
             &Damage,
+            &LightningStrikeCount,
             &DamageTrackerKind,
             &GlobalTransform,
+            Option<&AppliesStatus>,
         ),
-        With<ThorLightningMarker>,
+        With<ThorsLightningMarker>,
+    >,
+    mut enemy_query: Query<
+        (&mut Health, &GlobalTransform, Option<&mut StatusEffects>),
+        With<Enemy>,
     >,
+    enemy_grid: Res<EnemyGrid>,
+    mut sound_event: MessageWriter<PlaySoundEffectEvent>,
 ) {
-    let Some((mut attack_cd, max_attack_cd, hitbox, damage, damage_tracker_kind, tf)) =
-        lightning_query.iter_mut().next()
-    else {
-        return;
-    };
-    for _ in 0..attack_cd.reset(**max_attack_cd) {
-        let found = false;
-        for (mut enemy_health, enemy_transform) in &mut enemy_query {
-            if overlapping(
-                *hitbox,
-                tf.translation().xy(),
-                TakeDamageHitbox(Circle { radius: 0. }),
-                enemy_transform.translation.xy(),
-            ) {
-                commands.spawn(thors_lightning_strike_bundle(
-                    enemy_transform.translation.x,
-                    enemy_transform.translation.y,
-                    *damage,
-                ));
-                spawn_damage_text(
-                    &mut commands,
-                    damage,
-                    &asset_server,
-                    enemy_transform.translation.xy(),
-                );
-                **enemy_health = enemy_health.saturating_sub(**damage);
-                damage_tracker.update(*damage_tracker_kind, **damage);
-                break;
-            }
+    let PlayerDamageRolls {
+        mut rng,
+        player_query,
+        mut damage_events,
+    } = rolls;
+    let damage_roll = player_query.single().expect("Err");
+    for (
+        mut attack_cd,
+        max_attack_cd,
+        range,
+        &damage,
+        strike_count,
+        &damage_tracker_kind,
+        transform,
+        applies_status,
+    ) in &mut lightning_query
+    {
+        if !attack_cd.is_ready(**max_attack_cd) {
+            continue;
         }
-        if !found {
+        let origin = transform.translation().xy();
+        let enemies_in_range: Vec<Entity> = enemy_grid
+            .query(origin, **range)
+            .filter(|entry| entry.pos.distance(origin) <= **range + entry.radius)
+            .filter(|entry| {
+                enemy_query
+                    .get(entry.item)
+                    .is_ok_and(|(health, ..)| **health > 0)
+            })
+            .map(|entry| entry.item)
+            .collect();
+        if enemies_in_range.is_empty() {
             attack_cd.wait();
+            continue;
+        }
+        for _ in 0..attack_cd.reset(**max_attack_cd) {
+            for &entity in enemies_in_range.choose_multiple(&mut **rng, **strike_count as usize) {
+                let Ok((mut health, enemy_transform, status_effects)) = enemy_query.get_mut(entity)
+                else {
+                    continue;
+                };
+                if **health == 0 {
+                    continue;
+                }
+                let pos = enemy_transform.translation().xy();
+                commands.spawn(thors_lightning_strike_bundle(pos.x, pos.y));
+                let (damage, crit) = roll_damage(damage, damage_roll, &mut rng);
+                let dealt = (**health).min(*damage);
+                **health -= dealt;
+                damage_tracker.update(damage_tracker_kind, dealt);
+                damage_events.write(PlayerDamageEvent {
+                    pos,
+                    damage,
+                    crit,
+                    kind: Some(damage_tracker_kind),
+                });
+                if let (Some(&applies_status), Some(mut status_effects)) =
+                    (applies_status, status_effects)
+                {
+                    status_effects.apply(*applies_status, Some(damage_tracker_kind));
+                }
+            }
+            sound_event.write(PlaySoundEffectEvent(SoundEffectKind::Skill(
+                SkillSound::LightningAttack,
+            )));
         }
     }
 }
-*/
+
 pub fn animate_lightning(
     mut lightning_query: Query<(&LifeTime, &mut Sprite), With<LightningEffectMarker>>,
 ) {
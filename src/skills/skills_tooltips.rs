@@ -10,13 +10,15 @@ pub enum SkillType {
     PassiveMovementSpeedIncrease,
     PassivePickUpRadiusIncrease,
     PassiveHealthIncrease,
+    PassiveCritChance,
+    PassiveArmor,
     ActiveOrbitingOrb,
     ActiveThorLightning,
 }
 
 /// Resource containing information about the various skill upgrades.
 #[derive(Resource, Deref)]
-pub struct SkillTooltips(pub [(SkillType, &'static str, &'static str); 6]);
+pub struct SkillTooltips(pub [(SkillType, &'static str, &'static str); 8]);
 
 impl Default for SkillTooltips {
     /// Default implentation for `SkillTooltips`
@@ -46,7 +48,17 @@ impl Default for SkillTooltips {
             (
                 SkillType::PassiveHealthIncrease,
                 "Vitality",
-                "Increase max health by 1.",
+                "Increase max health by 10.",
+            ),
+            (
+                SkillType::PassiveCritChance,
+                "Precision",
+                "Increase Critical Hit Chance By 5%.",
+            ),
+            (
+                SkillType::PassiveArmor,
+                "Toughness",
+                "Reduce Damage Taken By 1.",
             ),
             (
                 SkillType::ActiveThorLightning,
@@ -56,3 +68,17 @@ impl Default for SkillTooltips {
         ])
     }
 }
+
+impl SkillTooltips {
+    pub fn skill_types(&self) -> Vec<SkillType> {
+        self.iter().map(|(skill_type, _, _)| *skill_type).collect()
+    }
+
+    /// Returns the tooltip of `skill_type`.
+    pub fn tooltip(&self, skill_type: SkillType) -> (SkillType, &'static str, &'static str) {
+        *self
+            .iter()
+            .find(|(tooltip_skill, _, _)| *tooltip_skill == skill_type)
+            .expect("Every SkillType has a tooltip")
+    }
+}
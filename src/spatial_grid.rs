@@ -0,0 +1,87 @@
+use bevy::{
+    math::{IVec2, Vec2},
+    platform::collections::HashMap,
+};
+
+/// An item stored in a [SpatialGrid], together with the circle it occupies.
+#[derive(Clone, Copy, Debug)]
+pub struct GridEntry<T> {
+    pub item: T,
+    pub pos: Vec2,
+    pub radius: f32,
+}
+
+/// Uniform grid that buckets items by position, so neighbourhood queries only look at nearby cells.
+/// Every item is stored in the cell containing its center, queries are widened by the largest radius
+/// that was inserted so that items reaching into the queried area are never missed.
+#[derive(Debug)]
+pub struct SpatialGrid<T> {
+    cell_size: f32,
+    cells: HashMap<IVec2, Vec<GridEntry<T>>>,
+    max_radius: f32,
+    len: usize,
+}
+
+impl<T: Copy> SpatialGrid<T> {
+    pub fn new(cell_size: f32) -> Self {
+        assert!(
+            cell_size > 0.,
+            "cell size of a spatial grid has to be positive"
+        );
+        Self {
+            cell_size,
+            cells: HashMap::default(),
+            max_radius: 0.,
+            len: 0,
+        }
+    }
+
+    /// Removes every item, cells that were used since the last clear keep their allocation.
+    pub fn clear(&mut self) {
+        self.cells.retain(|_, cell| {
+            let used = !cell.is_empty();
+            cell.clear();
+            used
+        });
+        self.max_radius = 0.;
+        self.len = 0;
+    }
+
+    pub fn insert(&mut self, item: T, pos: Vec2, radius: f32) {
+        self.cells
+            .entry(self.cell(pos))
+            .or_default()
+            .push(GridEntry { item, pos, radius });
+        self.max_radius = self.max_radius.max(radius);
+        self.len += 1;
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Every item in the grid, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &GridEntry<T>> {
+        self.cells.values().flatten()
+    }
+
+    /// Items whose circle might overlap the circle at `pos` with `radius`.
+    /// This is a broad phase, callers still have to do the exact overlap check.
+    pub fn query(&self, pos: Vec2, radius: f32) -> impl Iterator<Item = &GridEntry<T>> {
+        let reach = Vec2::splat(radius + self.max_radius);
+        let min = self.cell(pos - reach);
+        let max = self.cell(pos + reach);
+        (min.x..=max.x)
+            .flat_map(move |x| (min.y..=max.y).map(move |y| IVec2::new(x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+    }
+
+    fn cell(&self, pos: Vec2) -> IVec2 {
+        (pos / self.cell_size).floor().as_ivec2()
+    }
+}
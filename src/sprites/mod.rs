@@ -1,7 +1,7 @@
 use bevy::ecs::component::Component;
 use bevy::prelude::*;
 
-#[derive(Component, Clone, Copy)]
+#[derive(Component, Clone, Copy, serde::Deserialize)]
 pub enum Item {
     XPOrb,
     Magnet,
@@ -41,9 +41,14 @@ pub const PLAYER_WIDTH: u32 = 16;
 pub const WIZARD_HEIGHT: u32 = 32;
 pub const WIZARD_WIDTH: u32 = 32;
 
-#[derive(Component, Clone, Copy)]
+pub const CHIEFTAIN_HEIGHT: u32 = 40;
+pub const CHIEFTAIN_WIDTH: u32 = 44;
+
+#[derive(Component, Clone, Copy, serde::Deserialize)]
 pub enum Character {
     Jotun,
+    JotunArcher,
+    JotunChieftain,
     Warrior,
     Wizard,
 }
@@ -52,6 +57,8 @@ impl Character {
     fn image_path(&self) -> &'static str {
         match self {
             Character::Jotun => "characters/jotun.png",
+            Character::JotunArcher => "characters/jotun_archer.png",
+            Character::JotunChieftain => "characters/jotun_chieftain.png",
             Character::Warrior => "characters/viking.png",
             Character::Wizard => "characters/wizard.png",
         }
@@ -59,7 +66,7 @@ impl Character {
 
     fn atlas_layout(&self) -> Option<(TextureAtlasLayout, usize)> {
         match self {
-            Character::Jotun => Some((
+            Character::Jotun | Character::JotunArcher => Some((
                 TextureAtlasLayout::from_grid(
                     UVec2::new(ENEMY_WIDTH, ENEMY_HEIGHT),
                     4,
@@ -69,6 +76,16 @@ impl Character {
                 ),
                 0,
             )),
+            Character::JotunChieftain => Some((
+                TextureAtlasLayout::from_grid(
+                    UVec2::new(CHIEFTAIN_WIDTH, CHIEFTAIN_HEIGHT),
+                    4,
+                    1,
+                    None,
+                    None,
+                ),
+                0,
+            )),
             Character::Warrior => Some((
                 TextureAtlasLayout::from_grid(
                     UVec2::new(PLAYER_WIDTH, PLAYER_HEIGHT),
@@ -84,7 +101,7 @@ impl Character {
     }
 }
 
-#[derive(Component, Clone, Copy)]
+#[derive(Component, Clone, Copy, serde::Deserialize)]
 pub enum Skill {
     PrimaryAttack,
     OrbJutsu,
@@ -106,12 +123,16 @@ impl Skill {
 
     fn atlas_layout(&self) -> Option<(TextureAtlasLayout, usize)> {
         match self {
+            Skill::LightningAttack => Some((
+                TextureAtlasLayout::from_grid(UVec2::new(22, 59), 2, 1, None, None),
+                0,
+            )),
             _ => None,
         }
     }
 }
 
-#[derive(Component, Clone, Copy)]
+#[derive(Component, Clone, Copy, serde::Deserialize)]
 pub enum SpriteKind {
     Item(Item),
     Character(Character),
@@ -136,13 +157,17 @@ impl SpriteKind {
     }
 }
 
+/// Color multiplied with the sprite of an entity once it is added.
+#[derive(Component, Deref, Clone, Copy)]
+pub struct SpriteTint(pub Color);
+
 pub fn add_sprite(
     asset_server: Res<AssetServer>,
-    mut query: Query<(&SpriteKind, Entity), Without<Sprite>>,
+    mut query: Query<(&SpriteKind, Entity, Option<&SpriteTint>), Without<Sprite>>,
     mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
     mut commands: Commands,
 ) {
-    for (&kind, ent) in &mut query {
+    for (&kind, ent, tint) in &mut query {
         let image_path = kind.image_path();
         let texture_atlas = kind.atlas_layout().map(|(layout, index)| TextureAtlas {
             layout: texture_atlas_layouts.add(layout),
@@ -151,6 +176,7 @@ pub fn add_sprite(
         let sprite = Sprite {
             image: asset_server.load(image_path),
             texture_atlas,
+            color: tint.map_or(Color::WHITE, |tint| **tint),
             ..default()
         };
         commands.entity(ent).try_insert(sprite);
@@ -1,23 +1,34 @@
 use crate::{
-    characters::{components::Stage, MobPlugin, PlayerPlugin},
+    characters::{
+        bosses::boss::pick_boss, components::Stage, wave_director::reset_wave_director, MobPlugin,
+        PlayerPlugin,
+    },
     cleanup,
     loot::loot_plugin::LootPlugin,
-    map::map_plugin::MapPlugin,
+    map::map_plugin::{chunks_around_camera_loaded, MapPlugin},
     mechanics::{
         cooldown::{handle_ingametime, reset_ingametime, CooldownPlugin},
         damage::DamagePlugin,
+        knockback::KnockbackPlugin,
         movement::{
             orbiting::{orbital_movement, update_orbital_position},
             ProjectilePlugin,
         },
+        spatial::SpatialGridPlugin,
+        status_effects::StatusEffectsPlugin,
     },
     prestige::save_game_plugin::SaveGamePlugin,
-    skills::SkillsPlugin,
-    tools::{damage_tracking::reset_stats, debug::DebugPlugin},
+    skills::{
+        skill_levels::reset_skill_levels, skill_offers::reset_reroll_banish_charges, SkillsPlugin,
+    },
+    tools::{damage_tracking::reset_stats, debug::DebugPlugin, rng::reset_game_rng},
     ui::{
-        in_game::{render_stop_watch, update_health_ui, update_xp_bar_and_level},
+        in_game::{
+            render_stop_watch, update_boss_health_bar, update_health_ui, update_xp_bar_and_level,
+        },
         level_up_plugin::LevelUpPlugin,
         loss_plugin::LossPlugin,
+        minimap::MinimapPlugin,
         pause_game_plugin::{check_if_paused, PauseGamePlugin},
         win_plguin::WinPlugin,
     },
@@ -56,17 +67,41 @@ impl Plugin for GamePlugin {
             PauseGamePlugin,
             MapPlugin,
             LossPlugin,
+            MinimapPlugin,
             WinPlugin,
             SaveGamePlugin,
             MobPlugin,
             PlayerPlugin,
         ))
-        .add_systems(OnEnter(STATE), (reset_ingametime, start_game))
+        .add_systems(
+            OnEnter(STATE),
+            (
+                reset_game_rng,
+                (
+                    reset_ingametime,
+                    reset_skill_levels,
+                    reset_reroll_banish_charges,
+                    reset_wave_director,
+                    pick_boss,
+                ),
+            )
+                .chain(),
+        )
+        .add_systems(
+            Update,
+            start_game.run_if(
+                in_state(STATE)
+                    .and(in_state(GameState::NotStarted))
+                    .and(chunks_around_camera_loaded),
+            ),
+        )
         .add_systems(OnExit(STATE), (cleanup::<cleanup::ExitGame>, reset_stats))
         .add_systems(Update, (update_health_ui).run_if(in_state(STATE)));
     }
 }
 
+/// Starts the run once the map around the player is spawned,
+/// so that nothing is spawned into or walks through trees and rocks that aren't there yet.
 pub fn start_game(mut state: ResMut<NextState<GameState>>) {
     state.set(GameState::Running);
 }
@@ -77,7 +112,15 @@ impl Plugin for RunningPlugin {
     fn build(&self, app: &mut App) {
         const STATE: GameState = GameState::Running;
         app.add_plugins(CooldownPlugin)
-            .add_plugins((DamagePlugin, DebugPlugin, ProjectilePlugin, SkillsPlugin))
+            .add_plugins((
+                DamagePlugin,
+                DebugPlugin,
+                KnockbackPlugin,
+                ProjectilePlugin,
+                SkillsPlugin,
+                SpatialGridPlugin,
+                StatusEffectsPlugin,
+            ))
             .add_systems(
                 Update,
                 ((
@@ -87,6 +130,7 @@ impl Plugin for RunningPlugin {
                     update_xp_bar_and_level,
                     update_cursor,
                     render_stop_watch,
+                    update_boss_health_bar,
                     check_if_paused,
                 ),)
                     .run_if(in_state(STATE)),
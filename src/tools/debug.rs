@@ -1,4 +1,4 @@
-use std::f32::consts::PI;
+use std::f32::consts::FRAC_PI_2;
 
 use bevy::prelude::*;
 
@@ -18,6 +18,14 @@ impl Plugin for DebugPlugin {
     }
 }
 
+/// Shape and rotation of the sector covered by a cone hitbox.
+/// [CircularSector] is symmetric around the y-axis, so it is rotated to point along `mid_angle`.
+pub fn cone_sector(cone: damage::Cone) -> (CircularSector, Quat) {
+    let sector = CircularSector::new(cone.mid_angle.length(), 2. * cone.angular_width);
+    let rotation = Quat::from_rotation_z(cone.mid_angle.to_angle() - FRAC_PI_2);
+    (sector, rotation)
+}
+
 fn show_weakness(
     mut commands: Commands,
     q: Query<(&TakeDamageHitbox, Entity), (With<Transform>, Without<ShowWeaknessHitbox>)>,
@@ -68,17 +76,13 @@ fn show_damaging(
                     ));
                 });
             }
-            DealDamageHitbox::Cone(damage::Cone {
-                mid_angle,
-                angular_width,
-            }) => {
-                let mut tf = Transform::from_xyz(0., 0., 100.);
-                tf.rotate_z(2. * PI - mid_angle.to_angle().rem_euclid(2. * PI));
+            DealDamageHitbox::Cone(cone) => {
+                let (sector, rotation) = cone_sector(*cone);
                 inent.with_children(|parent| {
                     parent.spawn((
-                        Mesh2d(meshes.add(CircularSector::new(mid_angle.length(), *angular_width))),
+                        Mesh2d(meshes.add(sector)),
                         MeshMaterial2d(color.clone()),
-                        tf,
+                        Transform::from_xyz(0., 0., 100.).with_rotation(rotation),
                     ));
                 });
             }
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use bevy::prelude::*;
 use rand::prelude::*;
 use rand::rngs::SmallRng;
@@ -6,7 +8,12 @@ pub struct RngPlugin;
 
 impl Plugin for RngPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(GameRng(SmallRng::from_entropy()));
+        let run_seed = RunSeed::random();
+        app.insert_resource(GameRng::new(run_seed.derive(RngStream::Gameplay)))
+            .insert_resource(CosmeticRng(GameRng::new(
+                run_seed.derive(RngStream::Cosmetic),
+            )))
+            .insert_resource(run_seed);
     }
 }
 
@@ -14,9 +21,68 @@ impl Plugin for RngPlugin {
 pub struct GameRng(SmallRng);
 
 impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        GameRng(SmallRng::seed_from_u64(seed))
+    }
+
     pub fn rand_vec(&mut self, inner_bound: f32, outer_bound: f32) -> Vec2 {
         let angle: f32 = self.gen_range(0.0..(2. * std::f32::consts::PI));
         let r: f32 = self.gen_range(inner_bound..=outer_bound);
         r * Vec2::new(angle.sin(), angle.cos())
     }
 }
+
+/// Random numbers that only change how things look, such as where damage numbers appear.
+/// Kept apart from the [GameRng] so that drawing them never changes the gameplay of a seeded run.
+#[derive(Resource, Deref, DerefMut)]
+pub struct CosmeticRng(GameRng);
+
+/// Independent random streams of a run, each seeded from the [RunSeed].
+#[derive(Clone, Copy)]
+pub enum RngStream {
+    Gameplay = 1,
+    Map = 2,
+    Cosmetic = 3,
+}
+
+/// Seed of the next or current run, the map and every gameplay random number are derived from it.
+/// The [crate::map::collision::CollisionGrid] and the flow field only depend on the map around the camera,
+/// not on when its chunks finish generating, so the same seed and the same inputs give the same run.
+/// Shown as 16 hex digits so that it can be shared and entered in the main menu.
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RunSeed(pub u64);
+
+impl RunSeed {
+    pub fn random() -> Self {
+        RunSeed(rand::thread_rng().gen())
+    }
+
+    /// Parses a seed as shown by [fmt::Display], leading zeros may be left out.
+    pub fn parse(text: &str) -> Option<Self> {
+        u64::from_str_radix(text.trim(), 16).ok().map(RunSeed)
+    }
+
+    /// Seed of `stream`, mixed with SplitMix64 so that the streams don't correlate.
+    pub fn derive(&self, stream: RngStream) -> u64 {
+        let mut z = self.0 ^ (stream as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl fmt::Display for RunSeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:016X}", self.0)
+    }
+}
+
+/// Reseeds the [GameRng] and the [CosmeticRng] from the [RunSeed] at the start of every run.
+pub fn reset_game_rng(
+    run_seed: Res<RunSeed>,
+    mut rng: ResMut<GameRng>,
+    mut cosmetic_rng: ResMut<CosmeticRng>,
+) {
+    *rng = GameRng::new(run_seed.derive(RngStream::Gameplay));
+    *cosmetic_rng = CosmeticRng(GameRng::new(run_seed.derive(RngStream::Cosmetic)));
+}
@@ -1,11 +1,17 @@
-use bevy::prelude::*;
+use bevy::{color::palettes::css, prelude::*};
 
 use crate::{
-    characters::components::{CurrentLevel, CurrentXP, Health, MaxHealth, Player, RequiredXP},
+    characters::{
+        bosses::boss::Boss,
+        components::{
+            CurrentLevel, CurrentXP, Health, HealthRegenCooldown, MaxHealth,
+            MaxHealthRegenCooldown, Player, RequiredXP,
+        },
+    },
     cleanup::{self, ExitGame},
     mechanics::cooldown::InGameTime,
-    SCALE,
 };
+use test_game::PLAYER_HEAL_AMOUNT;
 #[derive(Component)]
 pub struct HealthUiSprite;
 
@@ -103,60 +109,102 @@ pub fn update_xp_bar_and_level(
 pub fn update_health_ui(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    mut q_health: Query<(&mut Transform, Entity), (With<HealthUiSprite>, Without<Player>)>,
-    q_player_health: Query<(&Health, &MaxHealth), (With<Player>, Without<HealthUiSprite>)>,
+    q_health: Query<Entity, With<HealthUiSprite>>,
+    q_player_health: Query<
+        (
+            &Health,
+            &MaxHealth,
+            Option<(&HealthRegenCooldown, &MaxHealthRegenCooldown)>,
+        ),
+        With<Player>,
+    >,
 ) {
-    let (player_health, player_max_health) = q_player_health.single().expect("Err");
-    for (mut _transform, entity) in q_health.iter_mut() {
+    let (player_health, player_max_health, regen) = q_player_health.single().expect("Err");
+    for entity in &q_health {
         commands.entity(entity).despawn();
     }
+    let health_percent = **player_health as f32 / **player_max_health as f32 * 100.0;
     commands
         .spawn((
             Node {
                 width: Val::Percent(100.0),
                 height: Val::Percent(5.0),
+                align_items: AlignItems::Center,
                 ..default()
             },
             HealthUiSprite,
             cleanup::ExitGame,
         ))
         .with_children(|child| {
-            for i in 0..**player_max_health {
-                if i < **player_health {
-                    child.spawn((
-                        Node {
-                            width: Val::Px(21.0 / SCALE),
-                            height: Val::Px(20.0 / SCALE),
-                            margin: UiRect {
-                                left: Val::Px(5.0),
-                                top: Val::Px(5.0),
-                                ..default()
-                            },
+            child
+                .spawn((
+                    Node {
+                        width: Val::Px(200.0),
+                        height: Val::Px(24.0),
+                        margin: UiRect {
+                            left: Val::Px(5.0),
+                            top: Val::Px(5.0),
                             ..default()
                         },
-                        ImageNode::new(asset_server.load("ui/heart.png")),
-                        HealthUiSprite,
-                    ));
-                } else {
-                    child.spawn((
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    BackgroundColor(Color::Srgba(Srgba {
+                        red: 0.0,
+                        green: 0.0,
+                        blue: 0.0,
+                        alpha: 0.5,
+                    })),
+                ))
+                .with_children(|grandchild| {
+                    grandchild.spawn((
                         Node {
-                            width: Val::Px(21.0 / SCALE),
-                            height: Val::Px(20.0 / SCALE),
-                            margin: UiRect {
-                                left: Val::Px(5.0),
-                                top: Val::Px(5.0),
-                                ..default()
-                            },
+                            position_type: PositionType::Absolute,
+                            left: Val::Px(0.0),
+                            width: Val::Percent(health_percent),
+                            height: Val::Percent(100.0),
                             ..default()
                         },
-                        ImageNode {
-                            image: asset_server.load("ui/heart.png"),
-                            color: Color::srgba(0.0, 0.0, 0.0, 0.7),
-                            ..Default::default()
+                        BackgroundColor(css::DARK_RED.into()),
+                    ));
+                    grandchild.spawn((
+                        Text::new(format!("{} / {}", **player_health, **player_max_health)),
+                        TextFont {
+                            font: asset_server.load("font/pixel-font.ttf").into(),
+                            font_size: FontSize::Px(20.0),
+                            ..default()
                         },
-                        HealthUiSprite,
+                        TextColor(Color::WHITE),
+                        TextLayout::justify(Justify::Center),
                     ));
-                }
+                });
+            if let Some((regen_cooldown, max_regen_cooldown)) = regen {
+                child.spawn((
+                    Node {
+                        margin: UiRect {
+                            left: Val::Px(10.0),
+                            top: Val::Px(5.0),
+                            ..default()
+                        },
+                        align_self: AlignSelf::Center,
+                        ..default()
+                    },
+                    Text::new(format!(
+                        "+{} in {}s",
+                        PLAYER_HEAL_AMOUNT,
+                        regen_cooldown
+                            .remaining(**max_regen_cooldown)
+                            .as_secs_f32()
+                            .ceil()
+                    )),
+                    TextFont {
+                        font: asset_server.load("font/pixel-font.ttf").into(),
+                        font_size: FontSize::Px(20.0),
+                        ..default()
+                    },
+                    TextColor(Color::WHITE),
+                ));
             }
         });
 }
@@ -206,3 +254,72 @@ pub fn render_stop_watch(
             ));
         });
 }
+
+#[derive(Component)]
+pub struct BossHealthBar;
+
+/// Shows the name and health of the boss at the top of the screen while it is alive.
+pub fn update_boss_health_bar(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    query_bar: Query<Entity, With<BossHealthBar>>,
+    query_boss: Query<(&Boss, &Health, &MaxHealth)>,
+) {
+    for entity in &query_bar {
+        commands.entity(entity).despawn();
+    }
+    let Some((boss, health, max_health)) = query_boss.iter().next() else {
+        return;
+    };
+    let health_percent = **health as f32 / **max_health as f32 * 100.0;
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(8.0),
+                left: Val::Percent(20.0),
+                width: Val::Percent(60.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            cleanup::ExitGame,
+            BossHealthBar,
+        ))
+        .with_children(|child| {
+            child.spawn((
+                Text::new(boss.name),
+                TextFont {
+                    font: asset_server.load("font/pixel-font.ttf").into(),
+                    font_size: FontSize::Px(24.0),
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                TextLayout::justify(Justify::Center),
+            ));
+            child
+                .spawn((
+                    Node {
+                        width: Val::Percent(100.0),
+                        height: Val::Px(16.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::Srgba(Srgba {
+                        red: 0.0,
+                        green: 0.0,
+                        blue: 0.0,
+                        alpha: 0.5,
+                    })),
+                ))
+                .with_children(|grandchild| {
+                    grandchild.spawn((
+                        Node {
+                            width: Val::Percent(health_percent),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(css::DARK_RED.into()),
+                    ));
+                });
+        });
+}
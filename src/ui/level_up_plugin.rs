@@ -1,25 +1,39 @@
-use bevy::{app::Plugin, color::palettes::css, prelude::*};
-use rand::prelude::*;
+use bevy::{app::Plugin, color::palettes::css, ecs::system::SystemParam, prelude::*};
 
 use crate::{
-    characters::components::{Health, MaxHealth, Player, PlayerDamage, XpPickUpRadius},
+    characters::components::{
+        Armor, DamageRoll, Health, MaxAttackCooldown, MaxHealth, Player, PlayerDamage,
+        XpPickUpRadius,
+    },
     cleanup,
-    mechanics::movement::orbiting::OrbitalRadius,
+    mechanics::{damage::BaseDamage, movement::orbiting::OrbitalRadius},
     skills::{
-        skills::{enable_thors_lightning_skill, spawn_new_orb},
+        bundles::thors_lightning::{LightningStrikeCount, ThorsLightningMarker},
+        skill_levels::{orb_jutsu_level, thors_lightning_level, SkillLevels},
+        skill_offers::{generate_offers, LevelUpOffers, RerollBanishCharges, OFFER_COUNT},
+        skills::{enable_thors_lightning_skill, spawn_orbs},
         skills_tooltips::{SkillTooltips, SkillType},
     },
     sound::events::{PlaySoundEffectEvent, SoundEffectKind, UiSound},
     AppState, GameRng, GameState, MovementSpeed,
 };
+
+use super::components::button::{custom_button, ButtonSize};
 pub struct LevelUpPlugin;
 
 impl Plugin for LevelUpPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::LevelUp), spawn_upgrade_selection_ui)
+        app.add_systems(OnEnter(GameState::LevelUp), generate_level_up_offers)
             .add_systems(
                 Update,
-                (handle_selection_cursor)
+                (
+                    spawn_upgrade_selection_ui.run_if(
+                        resource_changed::<LevelUpOffers>
+                            .or(resource_changed::<RerollBanishCharges>),
+                    ),
+                    handle_selection_cursor,
+                    handle_level_up_actions,
+                )
                     .run_if(in_state(AppState::InGame))
                     .run_if(in_state(GameState::LevelUp)),
             )
@@ -36,38 +50,115 @@ pub struct SelectedItemType(pub SkillType);
 #[derive(Component)]
 pub struct LevelUpUi;
 
+#[derive(Component, Clone, Copy)]
+pub enum LevelUpAction {
+    Reroll,
+    Banish,
+}
+
+/// Flat damage added to the primary attack per level of [SkillType::PassiveDamageIncrease].
+const PASSIVE_DAMAGE_INCREASE: u32 = 1;
+
+/// Multiplier applied per level of the movement speed and pickup radius passives.
+const PASSIVE_MULTIPLIER: f32 = 1.1;
+
+/// Max health added per level of [SkillType::PassiveHealthIncrease].
+const PASSIVE_HEALTH_INCREASE: u32 = 10;
+
+/// Crit chance added per level of [SkillType::PassiveCritChance].
+const PASSIVE_CRIT_CHANCE_INCREASE: f32 = 0.05;
+
+/// Armor added per level of [SkillType::PassiveArmor].
+const PASSIVE_ARMOR_INCREASE: u32 = 1;
+
+/// The offers on the level up screen and everything that rolls, rerolls or banishes them.
+#[derive(SystemParam)]
+pub struct OfferRolls<'w> {
+    item_tooltips: Res<'w, SkillTooltips>,
+    skill_levels: ResMut<'w, SkillLevels>,
+    charges: ResMut<'w, RerollBanishCharges>,
+    offers: ResMut<'w, LevelUpOffers>,
+    rng: ResMut<'w, GameRng>,
+}
+
+/// The offers on the level up screen and what their cards show.
+#[derive(SystemParam)]
+pub struct OfferCards<'w> {
+    item_tooltips: Res<'w, SkillTooltips>,
+    skill_levels: Res<'w, SkillLevels>,
+    offers: Res<'w, LevelUpOffers>,
+    charges: Res<'w, RerollBanishCharges>,
+}
+
+/// Active skills that picking their offer spawns or levels up.
+#[derive(SystemParam)]
+pub struct ActiveSkillQueries<'w, 's> {
+    orb_query: Query<'w, 's, Entity, With<OrbitalRadius>>,
+    lightning_query: Query<
+        'w,
+        's,
+        (
+            &'static mut LightningStrikeCount,
+            &'static mut BaseDamage,
+            &'static mut MaxAttackCooldown,
+        ),
+        With<ThorsLightningMarker>,
+    >,
+}
+
+/// Generates a fresh set of offers every time the player levels up.
+pub fn generate_level_up_offers(rolls: OfferRolls, mut game_state: ResMut<NextState<GameState>>) {
+    let OfferRolls {
+        item_tooltips,
+        skill_levels,
+        mut charges,
+        mut offers,
+        mut rng,
+    } = rolls;
+    charges.banishing = false;
+    **offers = generate_offers(
+        &mut rng,
+        &item_tooltips.skill_types(),
+        &skill_levels,
+        &charges.banished,
+        OFFER_COUNT,
+    );
+    if offers.is_empty() {
+        game_state.set(GameState::Running);
+    }
+}
+
+/// Rebuilds the level up screen whenever the offers or the reroll and banish charges change.
 pub fn spawn_upgrade_selection_ui(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     ui_query: Query<Entity, With<LevelUpUi>>,
-    item_tooltips: ResMut<SkillTooltips>,
-    mut rng: ResMut<GameRng>,
+    cards: OfferCards,
+    player_query: Query<
+        (
+            &XpPickUpRadius,
+            &MovementSpeed,
+            &PlayerDamage,
+            &MaxHealth,
+            &DamageRoll,
+            &Armor,
+        ),
+        With<Player>,
+    >,
 ) {
+    let OfferCards {
+        item_tooltips,
+        skill_levels,
+        offers,
+        charges,
+    } = cards;
     for entity in &ui_query {
         commands.entity(entity).despawn();
     }
-    let mut generated_indexes: Vec<usize> = Vec::new();
-    for _ in 0..3 {
-        let mut rand_index = rng.gen_range(0..item_tooltips.len() - generated_indexes.len());
-        for gen_index in &generated_indexes {
-            if *gen_index <= rand_index {
-                rand_index += 1;
-            } else {
-                break;
-            }
-        }
-        generated_indexes.push(rand_index);
-        generated_indexes.sort();
+    if offers.is_empty() {
+        return;
     }
-    generated_indexes.shuffle(&mut **rng);
-    println!(
-        "The random generated skill indexes are: {:?}",
-        generated_indexes,
-    );
-    let randomly_skill_selection: Vec<(SkillType, &'static str, &'static str)> = generated_indexes
-        .iter()
-        .map(|index| item_tooltips[*index])
-        .collect();
+    let player_stats = player_query.single().expect("Err");
     commands
         .spawn((
             Node {
@@ -75,7 +166,7 @@ pub fn spawn_upgrade_selection_ui(
                 height: Val::Percent(100.0),
                 align_items: AlignItems::Center,
                 justify_content: JustifyContent::Center,
-                flex_direction: FlexDirection::Row,
+                flex_direction: FlexDirection::Column,
                 ..default()
             },
             BackgroundColor(Color::Srgba(Srgba {
@@ -87,48 +178,203 @@ pub fn spawn_upgrade_selection_ui(
             LevelUpUi,
             cleanup::ExitLevelUpScreen,
         ))
-        .with_children(|child| {
-            randomly_skill_selection
-                .iter()
-                .for_each(|(item_type, title, description)| {
-                    child
-                        .spawn((
-                            Node {
-                                width: Val::Percent(20.0),
-                                height: Val::Percent(40.0),
-                                align_items: AlignItems::Center,
-                                margin: UiRect::all(Val::Px(40.0)),
-                                flex_direction: FlexDirection::Column,
-                                ..default()
-                            },
-                            Button,
-                            BackgroundColor(css::DARK_GRAY.into()),
-                            SelectedItemType(*item_type),
-                        ))
-                        .with_children(|text_child| {
-                            text_child.spawn((
-                                Text::new(*title),
-                                TextFont {
-                                    font: asset_server.load("font/pixel-font.ttf").into(),
-                                    font_size: FontSize::Px(28.0),
-                                    ..Default::default()
-                                },
-                                TextColor(Color::WHITE),
-                            ));
-                            text_child.spawn((
-                                Text::new(*description),
-                                TextFont {
-                                    font: asset_server.load("font/pixel-font.ttf").into(),
-                                    font_size: FontSize::Px(18.0),
-                                    ..Default::default()
+        .with_children(|parent| {
+            parent
+                .spawn(Node {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(50.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    flex_direction: FlexDirection::Row,
+                    ..default()
+                })
+                .with_children(|child| {
+                    offers.iter().for_each(|item_type| {
+                        let (_, title, description) = item_tooltips.tooltip(*item_type);
+                        let rarity = item_type.rarity();
+                        let level = skill_levels.level(*item_type);
+                        child
+                            .spawn((
+                                Node {
+                                    width: Val::Percent(20.0),
+                                    height: Val::Percent(80.0),
+                                    align_items: AlignItems::Center,
+                                    margin: UiRect::all(Val::Px(40.0)),
+                                    flex_direction: FlexDirection::Column,
+                                    ..default()
                                 },
-                                TextColor(Color::WHITE),
-                            ));
-                        });
+                                Button,
+                                BackgroundColor(rarity.card_color()),
+                                SelectedItemType(*item_type),
+                            ))
+                            .with_children(|text_child| {
+                                text_child.spawn((
+                                    Text::new(title),
+                                    TextFont {
+                                        font: asset_server.load("font/pixel-font.ttf").into(),
+                                        font_size: FontSize::Px(28.0),
+                                        ..Default::default()
+                                    },
+                                    TextColor(Color::WHITE),
+                                ));
+                                text_child.spawn((
+                                    Text::new(rarity.name()),
+                                    TextFont {
+                                        font: asset_server.load("font/pixel-font.ttf").into(),
+                                        font_size: FontSize::Px(16.0),
+                                        ..Default::default()
+                                    },
+                                    TextColor(css::GOLD.into()),
+                                ));
+                                text_child.spawn((
+                                    Text::new(format!("Lv {} -> {}", level, level + 1)),
+                                    TextFont {
+                                        font: asset_server.load("font/pixel-font.ttf").into(),
+                                        font_size: FontSize::Px(20.0),
+                                        ..Default::default()
+                                    },
+                                    TextColor(css::ORANGE.into()),
+                                ));
+                                text_child.spawn((
+                                    Text::new(stat_change_text(*item_type, level, player_stats)),
+                                    TextFont {
+                                        font: asset_server.load("font/pixel-font.ttf").into(),
+                                        font_size: FontSize::Px(18.0),
+                                        ..Default::default()
+                                    },
+                                    TextColor(css::LIGHT_GREEN.into()),
+                                ));
+                                text_child.spawn((
+                                    Text::new(description),
+                                    TextFont {
+                                        font: asset_server.load("font/pixel-font.ttf").into(),
+                                        font_size: FontSize::Px(18.0),
+                                        ..Default::default()
+                                    },
+                                    TextColor(Color::WHITE),
+                                ));
+                            });
+                    });
+                });
+            parent
+                .spawn(Node {
+                    width: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(40.0),
+                    ..default()
+                })
+                .with_children(|button_child| {
+                    custom_button(
+                        button_child,
+                        &asset_server,
+                        LevelUpAction::Reroll,
+                        action_color(charges.rerolls),
+                        Color::WHITE,
+                        format!("Reroll [{}]", charges.rerolls),
+                        ButtonSize::Large,
+                    );
+                    let banish_text = if charges.banishing {
+                        "Pick a card to banish".to_string()
+                    } else {
+                        format!("Banish [{}]", charges.banishes)
+                    };
+                    custom_button(
+                        button_child,
+                        &asset_server,
+                        LevelUpAction::Banish,
+                        action_color(charges.banishes),
+                        Color::WHITE,
+                        banish_text,
+                        ButtonSize::Large,
+                    );
                 });
         });
 }
 
+/// Color of a reroll or banish button, grayed out once the charges are used up.
+fn action_color(charges: u32) -> Color {
+    if charges > 0 {
+        css::MIDNIGHT_BLUE.into()
+    } else {
+        css::GRAY.into()
+    }
+}
+
+/// Describes the concrete stat change from picking `skill` at its current `level`.
+fn stat_change_text(
+    skill: SkillType,
+    level: u32,
+    (pick_up_radius, movement_speed, player_damage, max_health, damage_roll, armor): (
+        &XpPickUpRadius,
+        &MovementSpeed,
+        &PlayerDamage,
+        &MaxHealth,
+        &DamageRoll,
+        &Armor,
+    ),
+) -> String {
+    match skill {
+        SkillType::PassiveDamageIncrease => format!(
+            "Damage: {} -> {}",
+            **player_damage,
+            **player_damage + PASSIVE_DAMAGE_INCREASE
+        ),
+        SkillType::PassiveMovementSpeedIncrease => format!(
+            "Speed: {:.0} -> {:.0}",
+            **movement_speed,
+            **movement_speed * PASSIVE_MULTIPLIER
+        ),
+        SkillType::PassivePickUpRadiusIncrease => format!(
+            "Pickup Radius: {:.0} -> {:.0}",
+            **pick_up_radius,
+            **pick_up_radius * PASSIVE_MULTIPLIER
+        ),
+        SkillType::PassiveHealthIncrease => format!(
+            "Max Health: {} -> {}",
+            **max_health,
+            **max_health + PASSIVE_HEALTH_INCREASE
+        ),
+        SkillType::PassiveCritChance => format!(
+            "Crit Chance: {:.0}% -> {:.0}%",
+            damage_roll.crit_chance * 100.,
+            (damage_roll.crit_chance + PASSIVE_CRIT_CHANCE_INCREASE) * 100.
+        ),
+        SkillType::PassiveArmor => {
+            format!("Armor: {} -> {}", **armor, **armor + PASSIVE_ARMOR_INCREASE)
+        }
+        SkillType::ActiveOrbitingOrb => {
+            let next = orb_jutsu_level(level + 1).expect("skill is not at max level");
+            match orb_jutsu_level(level) {
+                Some(current) => format!(
+                    "Orbs: {} -> {}\nDamage: {} -> {}",
+                    current.orb_count, next.orb_count, current.damage, next.damage
+                ),
+                None => format!("Orbs: {}\nDamage: {}", next.orb_count, next.damage),
+            }
+        }
+        SkillType::ActiveThorLightning => {
+            let next = thors_lightning_level(level + 1).expect("skill is not at max level");
+            match thors_lightning_level(level) {
+                Some(current) => format!(
+                    "Strikes: {} -> {}\nDamage: {} -> {}\nCooldown: {:.1}s -> {:.1}s",
+                    current.strikes,
+                    next.strikes,
+                    current.damage,
+                    next.damage,
+                    current.cooldown_secs,
+                    next.cooldown_secs
+                ),
+                None => format!(
+                    "Strikes: {}\nDamage: {}\nCooldown: {:.1}s",
+                    next.strikes, next.damage, next.cooldown_secs
+                ),
+            }
+        }
+    }
+}
+
 pub fn handle_selection_cursor(
     mut commands: Commands,
     mut interaction_query: Query<
@@ -142,20 +388,36 @@ pub fn handle_selection_cursor(
             &mut PlayerDamage,
             &mut Health,
             &mut MaxHealth,
+            &mut DamageRoll,
+            &mut Armor,
             Entity,
         ),
         With<Player>,
     >,
-    mut orb_query: Query<Entity, With<OrbitalRadius>>,
+    skills: ActiveSkillQueries,
+    rolls: OfferRolls,
     mut game_state: ResMut<NextState<GameState>>,
     mut sound_event: MessageWriter<PlaySoundEffectEvent>,
 ) {
+    let ActiveSkillQueries {
+        mut orb_query,
+        mut lightning_query,
+    } = skills;
+    let OfferRolls {
+        item_tooltips,
+        mut skill_levels,
+        mut charges,
+        mut offers,
+        mut rng,
+    } = rolls;
     let (
         mut pick_up_radius,
         mut movement_speed,
         mut player_damage,
         mut health,
         mut max_health,
+        mut damage_roll,
+        mut armor,
         player_entity,
     ) = player_query.single_mut().expect("Err");
     for (interaction, item_type, mut background_color) in &mut interaction_query {
@@ -164,30 +426,60 @@ pub fn handle_selection_cursor(
                 sound_event.write(PlaySoundEffectEvent(SoundEffectKind::Ui(
                     UiSound::ClickButtonSound,
                 )));
+                if charges.banishing {
+                    banish_offer(
+                        **item_type,
+                        &mut charges,
+                        &mut offers,
+                        &item_tooltips,
+                        &skill_levels,
+                        &mut rng,
+                    );
+                    if offers.is_empty() {
+                        game_state.set(GameState::Running);
+                    }
+                    continue;
+                }
+                let level = skill_levels.level_up(**item_type);
                 match **item_type {
                     SkillType::PassiveDamageIncrease => {
-                        **player_damage += 1;
+                        **player_damage += PASSIVE_DAMAGE_INCREASE;
                         println!("damage increase to: {}", **player_damage);
                     }
                     SkillType::PassiveMovementSpeedIncrease => {
-                        **movement_speed *= 1.1;
+                        **movement_speed *= PASSIVE_MULTIPLIER;
                         println!("Movement speed increased to: {}", **movement_speed);
                     }
                     SkillType::PassivePickUpRadiusIncrease => {
-                        **pick_up_radius *= 1.1;
+                        **pick_up_radius *= PASSIVE_MULTIPLIER;
                         println!("Pickup radius increased to: {}", **pick_up_radius);
                     }
                     SkillType::ActiveOrbitingOrb => {
-                        println!("Orb jutsu");
-                        spawn_new_orb(&mut commands, player_entity, &mut orb_query);
+                        spawn_orbs(
+                            &mut commands,
+                            player_entity,
+                            &mut orb_query,
+                            orb_jutsu_level(level).expect("Orb Jutsu is learned"),
+                        );
                     }
                     SkillType::PassiveHealthIncrease => {
-                        **health += 1;
-                        **max_health += 1;
+                        **health += PASSIVE_HEALTH_INCREASE;
+                        **max_health += PASSIVE_HEALTH_INCREASE;
                         println!("health increased to: {}", **health);
                     }
+                    SkillType::PassiveCritChance => {
+                        damage_roll.crit_chance += PASSIVE_CRIT_CHANCE_INCREASE;
+                    }
+                    SkillType::PassiveArmor => {
+                        **armor += PASSIVE_ARMOR_INCREASE;
+                    }
                     SkillType::ActiveThorLightning => {
-                        enable_thors_lightning_skill(&mut commands, player_entity);
+                        enable_thors_lightning_skill(
+                            &mut commands,
+                            player_entity,
+                            &mut lightning_query,
+                            thors_lightning_level(level).expect("Thor's Lightning is learned"),
+                        );
                     }
                 }
                 game_state.set(GameState::Running);
@@ -196,9 +488,95 @@ pub fn handle_selection_cursor(
                 sound_event.write(PlaySoundEffectEvent(SoundEffectKind::Ui(
                     UiSound::HoverButtonSound,
                 )));
-                *background_color = css::GRAY.into();
+                *background_color = if charges.banishing {
+                    css::DARK_RED.into()
+                } else {
+                    item_type.rarity().hover_color()
+                };
+            }
+            Interaction::None => *background_color = item_type.rarity().card_color(),
+        }
+    }
+}
+
+/// Removes `skill` from the pool for the rest of the run and replaces its card with a new offer.
+fn banish_offer(
+    skill: SkillType,
+    charges: &mut RerollBanishCharges,
+    offers: &mut LevelUpOffers,
+    item_tooltips: &SkillTooltips,
+    skill_levels: &SkillLevels,
+    rng: &mut GameRng,
+) {
+    charges.banishing = false;
+    charges.banishes -= 1;
+    charges.banished.insert(skill);
+    let mut excluded = charges.banished.clone();
+    excluded.extend(offers.iter().copied());
+    let replacement = generate_offers(
+        rng,
+        &item_tooltips.skill_types(),
+        skill_levels,
+        &excluded,
+        1,
+    );
+    offers.retain(|offer| *offer != skill);
+    offers.extend(replacement);
+}
+
+pub fn handle_level_up_actions(
+    mut interaction_query: Query<
+        (&Interaction, &LevelUpAction, &mut BackgroundColor),
+        (Changed<Interaction>, With<Button>),
+    >,
+    rolls: OfferRolls,
+    mut sound_event: MessageWriter<PlaySoundEffectEvent>,
+) {
+    let OfferRolls {
+        item_tooltips,
+        skill_levels,
+        mut charges,
+        mut offers,
+        mut rng,
+    } = rolls;
+    for (interaction, action, mut background_color) in &mut interaction_query {
+        let remaining = match action {
+            LevelUpAction::Reroll => charges.rerolls,
+            LevelUpAction::Banish => charges.banishes,
+        };
+        match interaction {
+            Interaction::Pressed => {
+                if remaining == 0 {
+                    continue;
+                }
+                sound_event.write(PlaySoundEffectEvent(SoundEffectKind::Ui(
+                    UiSound::ClickButtonSound,
+                )));
+                match action {
+                    LevelUpAction::Reroll => {
+                        charges.rerolls -= 1;
+                        charges.banishing = false;
+                        **offers = generate_offers(
+                            &mut rng,
+                            &item_tooltips.skill_types(),
+                            &skill_levels,
+                            &charges.banished,
+                            OFFER_COUNT,
+                        );
+                    }
+                    LevelUpAction::Banish => charges.banishing = !charges.banishing,
+                }
+            }
+            Interaction::Hovered => {
+                if remaining == 0 {
+                    continue;
+                }
+                sound_event.write(PlaySoundEffectEvent(SoundEffectKind::Ui(
+                    UiSound::HoverButtonSound,
+                )));
+                *background_color = css::ORANGE.into();
             }
-            Interaction::None => *background_color = css::DARK_GRAY.into(),
+            Interaction::None => *background_color = action_color(remaining),
         }
     }
 }
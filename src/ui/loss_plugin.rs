@@ -3,7 +3,7 @@ use bevy::{app::Plugin, color::palettes::css, prelude::*};
 use crate::{
     cleanup,
     sound::events::{PlaySoundEffectEvent, SoundEffectKind, UiSound},
-    tools::damage_tracking::DamageTracker,
+    tools::{damage_tracking::DamageTracker, rng::RunSeed},
     AppState, GameState,
 };
 
@@ -33,6 +33,7 @@ pub fn spawn_loss_ui(
     ui_query: Query<Entity, With<LossUi>>,
     asset_server: Res<AssetServer>,
     damage_tracker: Res<DamageTracker>,
+    run_seed: Res<RunSeed>,
 ) {
     for entity in &ui_query {
         commands.entity(entity).despawn();
@@ -85,6 +86,16 @@ pub fn spawn_loss_ui(
                         TextColor(css::DARK_RED.into()),
                         TextLayout::justify(Justify::Center),
                     ));
+                    grandchild.spawn((
+                        Text::new(format!("Seed: {}", *run_seed)),
+                        TextFont {
+                            font: asset_server.load("font/pixel-font.ttf").into(),
+                            font_size: FontSize::Px(25.0),
+                            ..Default::default()
+                        },
+                        TextColor(css::WHITE.into()),
+                        TextLayout::justify(Justify::Center),
+                    ));
                     grandchild
                         .spawn(Node {
                             width: Val::Percent(100.),
@@ -0,0 +1,328 @@
+use bevy::{
+    asset::RenderAssetUsages,
+    color::palettes::css,
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+use crate::{
+    characters::{
+        bosses::boss::Boss,
+        components::{Enemy, Player},
+    },
+    cleanup,
+    loot::{loot::LootId, xp::XP},
+    map::map_plugin::{world_to_tile, MapNoise, TileSheetIndex},
+    AppState, GameState,
+};
+
+/// Side length of the minimap image in pixels.
+const MINIMAP_PIXELS: u32 = 64;
+/// Every pixel of the minimap shows the ground of one tile out of this many tiles in each direction.
+const TILES_PER_PIXEL: i32 = 2;
+/// Side length of the minimap on screen at scale 1.
+const MINIMAP_SIZE: f32 = 160.;
+const MIN_MINIMAP_SCALE: f32 = 0.5;
+const MAX_MINIMAP_SCALE: f32 = 2.;
+const MINIMAP_SCALE_STEP: f32 = 0.25;
+const DOT_SIZE: f32 = 4.;
+const BOSS_DOT_SIZE: f32 = 8.;
+
+/// Whether the minimap is shown and how large it is, toggled with M and scaled with - and =.
+#[derive(Resource)]
+pub struct MinimapSettings {
+    pub visible: bool,
+    pub scale: f32,
+}
+
+impl Default for MinimapSettings {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            scale: 1.,
+        }
+    }
+}
+
+/// The ground image of the minimap, `center` is the tile at its center when it was last drawn.
+#[derive(Resource)]
+struct MinimapImage {
+    handle: Handle<Image>,
+    center: Option<IVec2>,
+}
+
+/// Root node of the minimap in the top right corner.
+#[derive(Component)]
+struct Minimap;
+
+/// Enemy, loot, XP orb or boss shown on the minimap.
+/// The dots are pooled, every frame they are moved onto the markers and the unused ones are hidden.
+#[derive(Component)]
+struct MinimapDot;
+
+/// Arrow on the edge of the minimap pointing towards a boss outside of it.
+#[derive(Component)]
+struct MinimapBossArrow;
+
+pub struct MinimapPlugin;
+
+impl Plugin for MinimapPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(MinimapSettings::default())
+            .add_systems(OnEnter(AppState::InGame), spawn_minimap)
+            .add_systems(
+                Update,
+                (
+                    handle_minimap_keys,
+                    draw_minimap_ground,
+                    update_minimap_dots.after(draw_minimap_ground),
+                )
+                    .run_if(in_state(GameState::Running)),
+            );
+    }
+}
+
+fn ground_color(ground: TileSheetIndex) -> Color {
+    match ground {
+        TileSheetIndex::MiddleGrassTile => Color::srgb_u8(33, 149, 26),
+        TileSheetIndex::MiddleSandTile => Color::srgb_u8(254, 202, 74),
+        TileSheetIndex::MiddleDirtTile => Color::srgb_u8(87, 54, 18),
+        _ => Color::BLACK,
+    }
+}
+
+fn spawn_minimap(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut images: ResMut<Assets<Image>>,
+    settings: Res<MinimapSettings>,
+) {
+    let image = Image::new_fill(
+        Extent3d {
+            width: MINIMAP_PIXELS,
+            height: MINIMAP_PIXELS,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    let handle = images.add(image);
+    commands.insert_resource(MinimapImage {
+        handle: handle.clone(),
+        center: None,
+    });
+    let minimap = commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(5.0),
+                right: Val::Px(5.0),
+                width: Val::Px(MINIMAP_SIZE * settings.scale),
+                height: Val::Px(MINIMAP_SIZE * settings.scale),
+                border: UiRect::all(Val::Px(2.0)),
+                overflow: Overflow::clip(),
+                ..default()
+            },
+            BorderColor::all(Color::BLACK),
+            ImageNode::new(handle),
+            if settings.visible {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            },
+            Minimap,
+            cleanup::ExitGame,
+        ))
+        .id();
+    commands.spawn((
+        dot_node(Vec2::splat(50.), DOT_SIZE),
+        BackgroundColor(css::WHITE.into()),
+        ChildOf(minimap),
+    ));
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            margin: UiRect::all(Val::Px(-BOSS_DOT_SIZE)),
+            ..default()
+        },
+        Text::new(">"),
+        TextFont {
+            font: asset_server.load("font/pixel-font.ttf").into(),
+            font_size: FontSize::Px(BOSS_DOT_SIZE * 2.),
+            ..default()
+        },
+        TextColor(css::MAGENTA.into()),
+        UiTransform::default(),
+        Visibility::Hidden,
+        MinimapBossArrow,
+        ChildOf(minimap),
+    ));
+}
+
+/// M toggles the minimap, - and = make it smaller and larger.
+fn handle_minimap_keys(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut settings: ResMut<MinimapSettings>,
+    mut minimap: Query<(&mut Node, &mut Visibility), With<Minimap>>,
+) {
+    if keys.just_pressed(KeyCode::KeyM) {
+        settings.visible = !settings.visible;
+    }
+    if keys.just_pressed(KeyCode::Minus) {
+        settings.scale = (settings.scale - MINIMAP_SCALE_STEP).max(MIN_MINIMAP_SCALE);
+    }
+    if keys.just_pressed(KeyCode::Equal) {
+        settings.scale = (settings.scale + MINIMAP_SCALE_STEP).min(MAX_MINIMAP_SCALE);
+    }
+    if !settings.is_changed() {
+        return;
+    }
+    for (mut node, mut visibility) in &mut minimap {
+        node.width = Val::Px(MINIMAP_SIZE * settings.scale);
+        node.height = Val::Px(MINIMAP_SIZE * settings.scale);
+        *visibility = if settings.visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+/// The tile at the center of the minimap, it only moves once the player has walked a full pixel.
+fn minimap_center(player_pos: Vec2) -> IVec2 {
+    world_to_tile(player_pos).div_euclid(IVec2::splat(TILES_PER_PIXEL)) * TILES_PER_PIXEL
+}
+
+/// Redraws the downsampled ground around the player whenever the center of the minimap moves.
+fn draw_minimap_ground(
+    mut minimap_image: ResMut<MinimapImage>,
+    mut images: ResMut<Assets<Image>>,
+    noise: Res<MapNoise>,
+    settings: Res<MinimapSettings>,
+    player: Query<&Transform, With<Player>>,
+) {
+    if !settings.visible {
+        return;
+    }
+    let center = minimap_center(
+        player
+            .single()
+            .expect("Expected a single player!")
+            .translation
+            .xy(),
+    );
+    if minimap_image.center == Some(center) && !noise.is_changed() {
+        return;
+    }
+    minimap_image.center = Some(center);
+    let Some(mut image) = images.get_mut(&minimap_image.handle) else {
+        return;
+    };
+    let half = MINIMAP_PIXELS as i32 / 2;
+    for x in 0..MINIMAP_PIXELS {
+        for y in 0..MINIMAP_PIXELS {
+            let tile = center + IVec2::new(x as i32 - half, half - y as i32) * TILES_PER_PIXEL;
+            let _ = image.set_color_at(x, y, ground_color(noise.ground(tile)));
+        }
+    }
+}
+
+/// Position of `pos` on the minimap in percent of its size, `None` outside the minimap.
+fn minimap_position(center: IVec2, pos: Vec2) -> Option<Vec2> {
+    let pixels = (world_to_tile(pos) - center).as_vec2() / TILES_PER_PIXEL as f32;
+    let percent = Vec2::new(
+        0.5 + pixels.x / MINIMAP_PIXELS as f32,
+        0.5 - pixels.y / MINIMAP_PIXELS as f32,
+    ) * 100.;
+    (percent.cmpge(Vec2::ZERO).all() && percent.cmple(Vec2::splat(100.)).all()).then_some(percent)
+}
+
+fn dot_node(percent: Vec2, size: f32) -> Node {
+    Node {
+        position_type: PositionType::Absolute,
+        left: Val::Percent(percent.x),
+        top: Val::Percent(percent.y),
+        width: Val::Px(size),
+        height: Val::Px(size),
+        margin: UiRect::all(Val::Px(-size / 2.)),
+        ..default()
+    }
+}
+
+/// Shows enemies, loot, XP orbs and the boss as dots on the minimap.
+/// A boss outside the minimap is shown as an arrow on the edge pointing towards it.
+/// Nodes are only touched when they change so that the UI layout isn't redone every frame.
+fn update_minimap_dots(
+    mut commands: Commands,
+    minimap_image: Res<MinimapImage>,
+    settings: Res<MinimapSettings>,
+    minimap: Query<Entity, With<Minimap>>,
+    mut dots: Query<(&mut Node, &mut BackgroundColor, &mut Visibility), With<MinimapDot>>,
+    mut boss_arrow: Query<
+        (&mut Node, &mut UiTransform, &mut Visibility),
+        (With<MinimapBossArrow>, Without<MinimapDot>),
+    >,
+    markers: Query<
+        (&Transform, Has<Boss>, Has<Enemy>, Has<LootId>),
+        Or<(With<Enemy>, With<LootId>, With<XP>)>,
+    >,
+) {
+    let (Ok(minimap), Some(center), true) =
+        (minimap.single(), minimap_image.center, settings.visible)
+    else {
+        return;
+    };
+    let mut dots = dots.iter_mut();
+    let mut boss_outside = None;
+    for (transform, is_boss, is_enemy, is_loot) in &markers {
+        let pos = transform.translation.xy();
+        let Some(percent) = minimap_position(center, pos) else {
+            if is_boss {
+                boss_outside = Some(pos);
+            }
+            continue;
+        };
+        let (size, color) = match (is_boss, is_enemy, is_loot) {
+            (true, _, _) => (BOSS_DOT_SIZE, css::MAGENTA),
+            (_, true, _) => (DOT_SIZE, css::RED),
+            (_, _, true) => (DOT_SIZE, css::YELLOW),
+            _ => (DOT_SIZE / 2., css::AQUA),
+        };
+        match dots.next() {
+            Some((mut node, mut background, mut visibility)) => {
+                node.set_if_neq(dot_node(percent, size));
+                background.set_if_neq(BackgroundColor(color.into()));
+                visibility.set_if_neq(Visibility::Inherited);
+            }
+            None => {
+                commands.spawn((
+                    dot_node(percent, size),
+                    BackgroundColor(color.into()),
+                    MinimapDot,
+                    ChildOf(minimap),
+                ));
+            }
+        }
+    }
+    for (_, _, mut visibility) in dots {
+        visibility.set_if_neq(Visibility::Hidden);
+    }
+
+    let Ok((mut node, mut ui_transform, mut visibility)) = boss_arrow.single_mut() else {
+        return;
+    };
+    let Some(pos) = boss_outside else {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+    let direction = (world_to_tile(pos) - center).as_vec2().normalize_or_zero();
+    // Scale the direction onto the square edge of the minimap, y points down in the UI.
+    let edge = direction / direction.abs().max_element().max(f32::EPSILON);
+    let percent = Vec2::new(0.5 + edge.x * 0.45, 0.5 - edge.y * 0.45) * 100.;
+    node.left = Val::Percent(percent.x);
+    node.top = Val::Percent(percent.y);
+    *ui_transform = UiTransform::from_rotation(Rot2::radians(-direction.to_angle()));
+    visibility.set_if_neq(Visibility::Inherited);
+}
@@ -3,6 +3,7 @@ mod effects;
 pub mod in_game;
 pub mod level_up_plugin;
 pub mod loss_plugin;
+pub mod minimap;
 pub mod pause_game_plugin;
 pub mod settings_plugin;
 pub mod start_menu;
@@ -1,6 +1,6 @@
 use crate::sound::events::{PlaySoundEffectEvent, SoundEffectKind, UiSound};
 use crate::{cleanup, AppState};
-use crate::{cleanup::ExitPauseScreen, GameState};
+use crate::{cleanup::ExitPauseScreen, tools::rng::RunSeed, GameState};
 use bevy::{app::Plugin, color::palettes::css, prelude::*};
 
 use super::components::button::{custom_button, ButtonSize};
@@ -41,6 +41,7 @@ pub fn render_pause_options_node(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     query: Query<Entity, With<ExitPauseScreen>>,
+    run_seed: Res<RunSeed>,
 ) {
     for entity in &query {
         commands.entity(entity).despawn();
@@ -76,6 +77,16 @@ pub fn render_pause_options_node(
                     BackgroundColor(css::DARK_GRAY.into()),
                 ))
                 .with_children(|grandchild| {
+                    grandchild.spawn((
+                        Text::new(format!("Seed: {}", *run_seed)),
+                        TextFont {
+                            font: asset_server.load("font/pixel-font.ttf").into(),
+                            font_size: FontSize::Px(25.0),
+                            ..Default::default()
+                        },
+                        TextColor(Color::WHITE),
+                        TextLayout::justify(Justify::Center),
+                    ));
                     custom_button(
                         grandchild,
                         &asset_server,
@@ -1,6 +1,12 @@
 use crate::sound::events::{PlaySoundEffectEvent, SoundEffectKind, UiSound};
+use crate::tools::rng::RunSeed;
 use crate::{cleanup, AppState};
 use bevy::color::palettes::css;
+use bevy::ecs::system::SystemParam;
+use bevy::input::{
+    keyboard::{Key, KeyboardInput},
+    ButtonState,
+};
 use bevy::prelude::*;
 use test_game::GAME_TITLE;
 
@@ -10,12 +16,24 @@ use super::components::button::{custom_button, ButtonSize};
 #[derive(Component, Clone, Copy)]
 enum MenuButtonAction {
     Play,
+    PlayWithSeed,
     Upgrade,
     ExitGame,
     Settings,
 }
 #[derive(Component)]
 struct MainMenuScreen;
+
+/// Overlay of the main menu where the seed of the next run is typed in.
+#[derive(Component)]
+struct SeedEntryScreen;
+
+/// Hex digits typed into the [SeedEntryScreen] so far.
+#[derive(Component, Default)]
+struct SeedInput(String);
+
+/// Length of a [RunSeed] in hex digits.
+const SEED_DIGITS: usize = 16;
 pub struct StartMenuPlugin<S: States> {
     pub state: S,
 }
@@ -27,7 +45,7 @@ impl<S: States> Plugin for StartMenuPlugin<S> {
         )
         .add_systems(
             Update,
-            handle_button_click.run_if(in_state(self.state.clone())),
+            (handle_button_click, handle_seed_input).run_if(in_state(self.state.clone())),
         )
         .add_systems(OnExit(AppState::MainMenu), cleanup::<MainMenuScreen>);
     }
@@ -84,6 +102,15 @@ pub fn render_start_menu(mut commands: Commands, asset_server: Res<AssetServer>)
                         "Start Game",
                         ButtonSize::Large,
                     );
+                    custom_button(
+                        grandchild,
+                        &asset_server,
+                        MenuButtonAction::PlayWithSeed,
+                        css::MIDNIGHT_BLUE,
+                        css::WHITE,
+                        "Play With Seed",
+                        ButtonSize::Large,
+                    );
                     custom_button(
                         grandchild,
                         &asset_server,
@@ -115,15 +142,122 @@ pub fn render_start_menu(mut commands: Commands, asset_server: Res<AssetServer>)
         });
 }
 
+fn render_seed_entry(commands: &mut Commands, asset_server: &AssetServer) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            BackgroundColor(Color::Srgba(Srgba {
+                red: 0.0,
+                green: 0.0,
+                blue: 0.0,
+                alpha: 0.9,
+            })),
+            GlobalZIndex(1),
+            SeedEntryScreen,
+            MainMenuScreen,
+        ))
+        .with_children(|child| {
+            child.spawn((
+                Text::new("Type a seed, Enter to play, Escape to cancel"),
+                TextFont {
+                    font: asset_server.load("font/pixel-font.ttf").into(),
+                    font_size: FontSize::Px(25.0),
+                    ..Default::default()
+                },
+                TextColor(css::WHITE.into()),
+            ));
+            child.spawn((
+                Text::new("_"),
+                TextFont {
+                    font: asset_server.load("font/pixel-font.ttf").into(),
+                    font_size: FontSize::Px(40.0),
+                    ..Default::default()
+                },
+                TextColor(css::ORANGE.into()),
+                SeedInput::default(),
+            ));
+        });
+}
+
+/// Edits the seed typed into the [SeedEntryScreen] and starts the run once Enter is pressed.
+fn handle_seed_input(
+    mut commands: Commands,
+    mut keyboard_input: MessageReader<KeyboardInput>,
+    mut seed_input: Query<(&mut SeedInput, &mut Text)>,
+    seed_entry_screen: Query<Entity, With<SeedEntryScreen>>,
+    mut run_seed: ResMut<RunSeed>,
+    mut app_state: ResMut<NextState<AppState>>,
+) {
+    let Ok((mut input, mut text)) = seed_input.single_mut() else {
+        keyboard_input.clear();
+        return;
+    };
+    for event in keyboard_input.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Character(characters) => {
+                for character in characters.chars().filter(char::is_ascii_hexdigit) {
+                    if input.0.len() < SEED_DIGITS {
+                        input.0.push(character.to_ascii_uppercase());
+                    }
+                }
+            }
+            Key::Backspace => {
+                input.0.pop();
+            }
+            Key::Enter => {
+                if let Some(seed) = RunSeed::parse(&input.0) {
+                    *run_seed = seed;
+                    app_state.set(AppState::InGame);
+                }
+            }
+            Key::Escape => {
+                for entity in &seed_entry_screen {
+                    commands.entity(entity).despawn();
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+    text.0 = format!("{}_", input.0);
+}
+
+/// The [RunSeed] of the next run and what it takes to open the [SeedEntryScreen] for it.
+#[derive(SystemParam)]
+struct SeedEntry<'w, 's> {
+    asset_server: Res<'w, AssetServer>,
+    seed_entry_screen: Query<'w, 's, (), With<SeedEntryScreen>>,
+    run_seed: ResMut<'w, RunSeed>,
+}
+
 fn handle_button_click(
+    mut commands: Commands,
     mut interaction_query: Query<
         (&Interaction, &MenuButtonAction, &mut BackgroundColor),
         (Changed<Interaction>, With<Button>),
     >,
+    seed_entry: SeedEntry,
     mut app_state: ResMut<NextState<AppState>>,
     mut sound_event: MessageWriter<PlaySoundEffectEvent>,
     mut exit: MessageWriter<AppExit>,
 ) {
+    let SeedEntry {
+        asset_server,
+        seed_entry_screen,
+        mut run_seed,
+    } = seed_entry;
     for (interaction, menu_button_action, mut background_color) in &mut interaction_query {
         match *interaction {
             Interaction::Pressed => {
@@ -131,7 +265,15 @@ fn handle_button_click(
                     UiSound::ClickButtonSound,
                 )));
                 match menu_button_action {
-                    MenuButtonAction::Play => app_state.set(AppState::InGame),
+                    MenuButtonAction::Play => {
+                        *run_seed = RunSeed::random();
+                        app_state.set(AppState::InGame);
+                    }
+                    MenuButtonAction::PlayWithSeed => {
+                        if seed_entry_screen.is_empty() {
+                            render_seed_entry(&mut commands, &asset_server);
+                        }
+                    }
                     MenuButtonAction::Upgrade => app_state.set(AppState::Upgrade),
                     MenuButtonAction::Settings => app_state.set(AppState::Settings),
                     MenuButtonAction::ExitGame => {
@@ -117,6 +117,18 @@ fn spawn_upgrade_ui(
                         &mut stats,
                         UpgradeOptions::MaximumHealth,
                     );
+                    upgrade_options_bundle(
+                        upgrade_child,
+                        &asset_server,
+                        &mut stats,
+                        UpgradeOptions::RerollBanish,
+                    );
+                    upgrade_options_bundle(
+                        upgrade_child,
+                        &asset_server,
+                        &mut stats,
+                        UpgradeOptions::CritChance,
+                    );
                     upgrade_options_bundle(
                         upgrade_child,
                         &asset_server,